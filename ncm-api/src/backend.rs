@@ -0,0 +1,346 @@
+use crate::error::{NcmError, Result};
+use crate::model::{Account, Lyrics, Song, Songlist};
+use crate::NcmClient;
+use std::collections::{HashMap, HashSet};
+
+/// 音乐服务后端，`Player` 通过它获取歌单、歌曲链接和歌词
+///
+/// `NcmClient` 为网易云音乐的实现，`MemoryBackend` 为不依赖网络的内存实现（用于测试和演示）
+#[allow(async_fn_in_trait)]
+pub trait MusicBackend {
+    /// 是否登录
+    fn is_login(&self) -> bool;
+
+    /// 登录的账号信息
+    fn login_account(&self) -> Option<Account>;
+
+    /// 尝试用保存的登录凭据登录，返回是否登录成功
+    async fn try_cookie_login(&mut self) -> Result<bool>;
+
+    /// 手机号 + 密码登录并保存登录凭据，成功后 `login_account` 返回登录的账号
+    ///
+    /// 与 `NcmClient::login_by_cellphone_password`（只更新 cookie）不同，登录后无需调用方再保存凭据和检查登录状态
+    async fn login_and_persist_by_cellphone_password(&mut self, phone: &str, password: &str) -> Result<()>;
+
+    /// 邮箱 + 密码登录并保存登录凭据，成功后 `login_account` 返回登录的账号
+    async fn login_and_persist_by_email(&mut self, email: &str, password: &str) -> Result<()>;
+
+    /// 登出
+    async fn logout(&mut self) -> Result<()>;
+
+    /// 获取用户所有歌单（缩略，不含歌曲）
+    async fn get_user_all_songlists(&self) -> Result<Vec<Songlist>>;
+
+    /// 分页获取用户歌单，同时返回是否还有下一页
+    async fn get_user_songlists(&self, offset: usize, limit: usize) -> Result<(Vec<Songlist>, bool)>;
+
+    /// 装载歌单内的所有歌曲
+    async fn load_songlist_songs(&self, songlist: &mut Songlist) -> Result<()>;
+
+    /// 检查歌曲是否可获取
+    async fn check_song_availability(&self, song_id: u64) -> Result<bool>;
+
+    /// 装载歌曲 url
+    async fn load_song_url(&self, song: &mut Song) -> Result<()>;
+
+    /// 获取歌曲的歌词
+    async fn get_song_lyrics(&self, song_id: u64) -> Result<Lyrics>;
+
+    /// 获取私人FM的下一批歌曲
+    async fn get_personal_fm(&self) -> Result<Vec<Song>>;
+
+    /// 将歌曲移出私人FM（“不喜欢”）
+    async fn fm_trash(&self, song_id: u64) -> Result<()>;
+
+    /// 获取心动模式歌曲列表，推荐歌曲的 `is_recommended` 为 true
+    async fn get_intelligence_list(&self, song_id: u64, songlist_id: u64) -> Result<Vec<Song>>;
+
+    /// 获取与歌曲相似的歌曲
+    async fn get_similar_songs(&self, song_id: u64) -> Result<Vec<Song>>;
+}
+
+impl MusicBackend for NcmClient {
+    fn is_login(&self) -> bool {
+        NcmClient::is_login(self)
+    }
+
+    fn login_account(&self) -> Option<Account> {
+        NcmClient::login_account(self)
+    }
+
+    async fn try_cookie_login(&mut self) -> Result<bool> {
+        NcmClient::try_cookie_login(self).await
+    }
+
+    async fn login_and_persist_by_cellphone_password(&mut self, phone: &str, password: &str) -> Result<()> {
+        NcmClient::login_by_cellphone_password(self, phone, password).await?;
+        self.store_cookie();
+        self.check_login_status().await
+    }
+
+    async fn login_and_persist_by_email(&mut self, email: &str, password: &str) -> Result<()> {
+        NcmClient::login_by_email(self, email, password).await?;
+        self.store_cookie();
+        self.check_login_status().await
+    }
+
+    async fn logout(&mut self) -> Result<()> {
+        NcmClient::logout(self).await
+    }
+
+    async fn get_user_all_songlists(&self) -> Result<Vec<Songlist>> {
+        NcmClient::get_user_all_songlists(self).await
+    }
+
+    async fn get_user_songlists(&self, offset: usize, limit: usize) -> Result<(Vec<Songlist>, bool)> {
+        NcmClient::get_user_songlists(self, offset, limit).await
+    }
+
+    async fn load_songlist_songs(&self, songlist: &mut Songlist) -> Result<()> {
+        NcmClient::load_songlist_songs(self, songlist).await
+    }
+
+    async fn check_song_availability(&self, song_id: u64) -> Result<bool> {
+        NcmClient::check_song_availability(self, song_id).await
+    }
+
+    async fn load_song_url(&self, song: &mut Song) -> Result<()> {
+        NcmClient::load_song_url(self, song).await
+    }
+
+    async fn get_song_lyrics(&self, song_id: u64) -> Result<Lyrics> {
+        NcmClient::get_song_lyrics(self, song_id).await
+    }
+
+    async fn get_personal_fm(&self) -> Result<Vec<Song>> {
+        NcmClient::get_personal_fm(self).await
+    }
+
+    async fn fm_trash(&self, song_id: u64) -> Result<()> {
+        NcmClient::fm_trash(self, song_id).await
+    }
+
+    async fn get_intelligence_list(&self, song_id: u64, songlist_id: u64) -> Result<Vec<Song>> {
+        NcmClient::get_intelligence_list(self, song_id, songlist_id).await
+    }
+
+    async fn get_similar_songs(&self, song_id: u64) -> Result<Vec<Song>> {
+        NcmClient::get_similar_songs(self, song_id).await
+    }
+}
+
+/// 内存中的音乐服务后端，所有数据由调用方预先填入
+#[derive(Debug, Default, Clone)]
+pub struct MemoryBackend {
+    /// 登录的账号，为 None 时表示未登录
+    pub account: Option<Account>,
+
+    /// 用户歌单（包括歌单内的歌曲）
+    pub songlists: Vec<Songlist>,
+
+    /// 歌曲 id -> 歌曲链接
+    pub song_urls: HashMap<u64, String>,
+
+    /// 歌曲 id -> 歌词
+    pub lyrics: HashMap<u64, Lyrics>,
+
+    /// 不可获取的歌曲 id（版权/会员/...限制）
+    pub unavailable_song_ids: HashSet<u64>,
+
+    /// 私人FM的歌曲，每次获取都返回全部（模拟无尽的推荐）
+    pub personal_fm_songs: Vec<Song>,
+
+    /// 心动模式推荐的歌曲，返回时均标记为推荐
+    pub intelligence_songs: Vec<Song>,
+
+    /// 相似歌曲，对任意歌曲都返回全部
+    pub similar_songs: Vec<Song>,
+
+    /// 可登录的账号：(手机号或邮箱, 密码, 账号信息)
+    pub credentials: Vec<(String, String, Account)>,
+
+    /// 保存的登录凭据对应的账号（模拟本地 cookie），登录成功时更新，登出时清除
+    pub saved_account: Option<Account>,
+}
+
+impl MemoryBackend {
+    pub fn new(account: Option<Account>, songlists: Vec<Songlist>) -> Self {
+        Self {
+            account,
+            songlists,
+            ..Default::default()
+        }
+    }
+
+    /// 按凭据登录，账号或密码错误时与网易云一致返回 502
+    fn login_by_credential(&mut self, name: &str, password: &str) -> Result<()> {
+        match self.credentials.iter().find(|(n, p, _)| n == name && p == password) {
+            Some((_, _, account)) => {
+                self.account = Some(account.clone());
+                self.saved_account = Some(account.clone());
+                Ok(())
+            },
+            None => Err(NcmError::ApiCode(502, format!("wrong password for account {}", name))),
+        }
+    }
+}
+
+impl MusicBackend for MemoryBackend {
+    fn is_login(&self) -> bool {
+        self.account.is_some()
+    }
+
+    fn login_account(&self) -> Option<Account> {
+        self.account.clone()
+    }
+
+    async fn try_cookie_login(&mut self) -> Result<bool> {
+        self.account = self.saved_account.clone();
+
+        Ok(self.account.is_some())
+    }
+
+    async fn login_and_persist_by_cellphone_password(&mut self, phone: &str, password: &str) -> Result<()> {
+        self.login_by_credential(phone, password)
+    }
+
+    async fn login_and_persist_by_email(&mut self, email: &str, password: &str) -> Result<()> {
+        self.login_by_credential(email, password)
+    }
+
+    async fn logout(&mut self) -> Result<()> {
+        self.account = None;
+        self.saved_account = None;
+
+        Ok(())
+    }
+
+    async fn get_user_all_songlists(&self) -> Result<Vec<Songlist>> {
+        self.get_user_songlists(0, self.songlists.len()).await.map(|(songlists, _)| songlists)
+    }
+
+    async fn get_user_songlists(&self, offset: usize, limit: usize) -> Result<(Vec<Songlist>, bool)> {
+        if !self.is_login() {
+            return Err(NcmError::NotLoggedIn);
+        }
+
+        // 与 `NcmClient` 一致，返回的歌单不含歌曲
+        let songlists = self
+            .songlists
+            .iter()
+            .skip(offset)
+            .take(limit)
+            .map(|songlist| Songlist {
+                songs: Vec::new(),
+                ..songlist.clone()
+            })
+            .collect();
+        let has_more = offset + limit < self.songlists.len();
+
+        Ok((songlists, has_more))
+    }
+
+    async fn load_songlist_songs(&self, songlist: &mut Songlist) -> Result<()> {
+        match self.songlists.iter().find(|s| s.id == songlist.id) {
+            Some(s) => {
+                songlist.songs = s.songs.clone();
+                Ok(())
+            },
+            None => Err(NcmError::ApiCode(404, format!("failed to load songs into songlist {}", songlist.id))),
+        }
+    }
+
+    async fn check_song_availability(&self, song_id: u64) -> Result<bool> {
+        Ok(!self.unavailable_song_ids.contains(&song_id))
+    }
+
+    async fn load_song_url(&self, song: &mut Song) -> Result<()> {
+        song.song_url = self.song_urls.get(&song.id).cloned();
+
+        Ok(())
+    }
+
+    async fn get_song_lyrics(&self, song_id: u64) -> Result<Lyrics> {
+        Ok(self.lyrics.get(&song_id).cloned().unwrap_or_default())
+    }
+
+    async fn get_personal_fm(&self) -> Result<Vec<Song>> {
+        if !self.is_login() {
+            return Err(NcmError::NotLoggedIn);
+        }
+
+        Ok(self.personal_fm_songs.clone())
+    }
+
+    async fn fm_trash(&self, _song_id: u64) -> Result<()> {
+        if !self.is_login() {
+            return Err(NcmError::NotLoggedIn);
+        }
+
+        Ok(())
+    }
+
+    async fn get_intelligence_list(&self, _song_id: u64, _songlist_id: u64) -> Result<Vec<Song>> {
+        if !self.is_login() {
+            return Err(NcmError::NotLoggedIn);
+        }
+
+        Ok(self
+            .intelligence_songs
+            .iter()
+            .map(|song| Song {
+                is_recommended: true,
+                ..song.clone()
+            })
+            .collect())
+    }
+
+    async fn get_similar_songs(&self, _song_id: u64) -> Result<Vec<Song>> {
+        Ok(self.similar_songs.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account() -> Account {
+        Account {
+            user_id: 1,
+            nickname: String::from("tester"),
+            vip_type: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn memory_backend_login_and_logout() {
+        let mut backend = MemoryBackend::default();
+        backend.credentials.push((String::from("13800000000"), String::from("password"), account()));
+        backend.credentials.push((String::from("tester@163.com"), String::from("password"), account()));
+        assert!(!backend.try_cookie_login().await.unwrap());
+
+        match backend.login_and_persist_by_cellphone_password("13800000000", "wrong").await {
+            Err(NcmError::ApiCode(502, _)) => {},
+            other => panic!("unexpected login result: {:?}", other),
+        }
+        assert!(!backend.is_login());
+        assert!(matches!(backend.get_user_songlists(0, 10).await, Err(NcmError::NotLoggedIn)));
+
+        backend.login_and_persist_by_cellphone_password("13800000000", "password").await.unwrap();
+        assert_eq!(backend.login_account(), Some(account()));
+
+        // 保存的凭据在下次启动时仍可登录
+        let mut restarted = MemoryBackend {
+            account: None,
+            ..backend.clone()
+        };
+        assert!(restarted.try_cookie_login().await.unwrap());
+
+        backend.logout().await.unwrap();
+        assert!(!backend.is_login());
+        assert!(!backend.try_cookie_login().await.unwrap());
+
+        backend.login_and_persist_by_email("tester@163.com", "password").await.unwrap();
+        assert!(backend.is_login());
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt::{Display, Formatter};
+
+pub type Result<T> = std::result::Result<T, NcmError>;
+
+/// ncm-api 的错误类型
+#[derive(Debug)]
+pub enum NcmError {
+    /// 网络请求失败
+    Network(reqwest::Error),
+
+    /// 上游 api 返回了非 200 的状态码 (code, msg)
+    ApiCode(i64, String),
+
+    /// 需要登录
+    NotLoggedIn,
+
+    /// cookie 已失效，需要重新登录
+    CookieExpired,
+
+    /// 响应中缺少字段或字段类型不符（json pointer 路径，如 `/data/0/url`）
+    Decode(String),
+
+    /// 响应不是合法的 json
+    Json(serde_json::Error),
+
+    /// 本地文件或子进程读写失败
+    Io(std::io::Error),
+}
+
+impl Display for NcmError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NcmError::Network(err) => write!(f, "network error: {}", err),
+            NcmError::ApiCode(code, msg) => write!(f, "{} (code {})", msg, code),
+            NcmError::NotLoggedIn => write!(f, "not logged in, use `login` to login first"),
+            NcmError::CookieExpired => write!(f, "login expired, please login again"),
+            NcmError::Decode(pointer) => write!(f, "failed to decode field `{}` from api response", pointer),
+            NcmError::Json(err) => write!(f, "invalid api response: {}", err),
+            NcmError::Io(err) => write!(f, "io error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for NcmError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            NcmError::Network(err) => Some(err),
+            NcmError::Json(err) => Some(err),
+            NcmError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for NcmError {
+    fn from(err: reqwest::Error) -> Self {
+        NcmError::Network(err)
+    }
+}
+
+impl From<serde_json::Error> for NcmError {
+    fn from(err: serde_json::Error) -> Self {
+        NcmError::Json(err)
+    }
+}
+
+impl From<std::io::Error> for NcmError {
+    fn from(err: std::io::Error) -> Self {
+        NcmError::Io(err)
+    }
+}
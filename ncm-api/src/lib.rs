@@ -1,27 +1,67 @@
+mod backend;
+mod error;
+mod lrc;
 pub mod model;
 mod responses;
 mod settings;
 
-use crate::model::{Account, FromJson, LyricLine, Lyrics, Song, Songlist};
+use crate::error::Result;
+use crate::model::{Account, Album, Artist, Comment, CommentPage, CommentSortType, FromJson, Lyrics, QualityLevel, ScrobbleRecord, SearchResult, SearchType, Song, Songlist, UploadProgress};
 use crate::responses::login::*;
 use crate::settings::Settings;
-use anyhow::{anyhow, Result};
 use chrono::Utc;
+use futures_util::StreamExt;
 use log::{debug, error};
-use regex::Regex;
-use reqwest::{Client, ClientBuilder};
+use reqwest::multipart::{Form, Part};
+use reqwest::{Body, Client, ClientBuilder};
 use serde_json::Value;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::fs::File;
+use std::future::Future;
 use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::task::Poll;
+use std::time::Duration;
 use tokio::process;
+use tokio_util::io::ReaderStream;
+
+pub use crate::backend::{MemoryBackend, MusicBackend};
+pub use crate::error::NcmError;
+
+/// 每页获取的用户歌单数量
+pub const USER_SONGLISTS_PAGE_SIZE: usize = 30;
+
+/// 每页获取的歌曲评论数量
+pub const SONG_COMMENTS_PAGE_SIZE: usize = 20;
+
+/// 每页获取的云盘歌曲数量
+pub const CLOUD_SONGS_PAGE_SIZE: usize = 200;
+
+/// 上传云盘时每次发送的字节数（每发送一块更新一次进度）
+const CLOUD_UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// 歌词缓存格式版本，歌词的解析或编码方式变化时递增（v2：逐字歌词、重写的 LRC 解析）
+const LYRICS_CACHE_VERSION: u32 = 2;
+
+/// 下载的封面图片边长（像素）
+const COVER_IMAGE_SIZE: u32 = 300;
+
+/// 本地听歌记录队列最多保存的记录数，超出时丢弃最早的记录
+const SCROBBLE_QUEUE_MAX_LEN: usize = 500;
+
+/// 上报听歌记录的请求超时，网络不通时尽快放弃并入队
+const SCROBBLE_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
 
 pub struct NcmClient {
     api_program_path: PathBuf,
     cookie_path: PathBuf,
     lyrics_path: PathBuf,
+    covers_path: PathBuf,
     settings_path: PathBuf,
+    scrobble_queue_path: PathBuf,
+    scrobble_queue_lock: Arc<tokio::sync::Mutex<()>>,
 
     api_child_process: Option<process::Child>,
     http_client: Client,
@@ -30,21 +70,28 @@ pub struct NcmClient {
     settings: Settings,
 
     login_account: Option<Account>,
+    liked_song_ids: HashSet<u64>,
+    liked_song_ids_version: usize, // 喜欢的歌曲每次变化时递增，界面据此判断是否需要刷新
 }
 
 impl NcmClient {
-    pub fn new(api_program_path: PathBuf, cookie_path: PathBuf, lyrics_path: PathBuf, settings_path: PathBuf) -> Self {
+    pub fn new(api_program_path: PathBuf, cookie_path: PathBuf, lyrics_path: PathBuf, covers_path: PathBuf, settings_path: PathBuf, scrobble_queue_path: PathBuf) -> Self {
         Self {
             api_program_path,
             cookie_path,
             lyrics_path,
+            covers_path,
             settings_path,
+            scrobble_queue_path,
+            scrobble_queue_lock: Arc::new(tokio::sync::Mutex::new(())),
             api_child_process: None,
             api_url: String::new(),
             http_client: ClientBuilder::new().no_proxy().build().expect("failed to build HTTP client"),
             cookie: String::new(),
             settings: Settings::default(),
             login_account: None,
+            liked_song_ids: HashSet::new(),
+            liked_song_ids_version: 0,
         }
     }
 
@@ -180,6 +227,13 @@ impl NcmClient {
 
         Ok(())
     }
+
+    /// 检查上游响应的状态码，非 200 时转换为对应的 `NcmError`
+    ///
+    /// `action` 描述失败的操作，如 `get album 123`
+    fn check_response_code(&self, v_response: &Value, action: &str) -> Result<()> {
+        check_response_code(v_response, action, &self.cookie)
+    }
 }
 
 // 登录 api
@@ -246,10 +300,10 @@ impl NcmClient {
                 debug!("get login qr key & url: {}, {}", uni_key, create_response.data.qrurl);
                 Ok((uni_key, create_response.data.qrurl))
             } else {
-                Err(anyhow!("failed to get login qr url"))
+                Err(NcmError::ApiCode(create_response.code as i64, String::from("failed to get login qr url")))
             }
         } else {
-            Err(anyhow!("failed to get login qr unikey"))
+            Err(NcmError::ApiCode(key_response.data.code as i64, String::from("failed to get login qr unikey")))
         }
     }
 
@@ -273,7 +327,71 @@ impl NcmClient {
         Ok(check_response.code)
     }
 
-    /// 获取登录状态
+    /// 发送手机验证码（`ctcode` 为国家码，中国大陆为 `86`）
+    pub async fn send_captcha(&self, phone: &str, ctcode: &str) -> Result<()> {
+        let captcha_response = self
+            .http_client
+            .post(format!("{}/captcha/sent?timestamp={}", &self.api_url, Utc::now().timestamp()))
+            .form(&[("phone", phone), ("ctcode", ctcode)])
+            .send()
+            .await?;
+
+        let v_captcha: Value = serde_json::from_slice(&captcha_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_captcha, "send captcha")?;
+
+        debug!("captcha sent to {}", phone);
+
+        Ok(())
+    }
+
+    /// 手机号+密码登录
+    ///
+    /// 登录成功后只更新 cookie ，之后需与二维码登录一样调用 `store_cookie()` 和 `check_login_status()`
+    pub async fn login_by_cellphone_password(&mut self, phone: &str, password: &str) -> Result<()> {
+        self.login_by_form("login/cellphone", &[("phone", phone), ("password", password)]).await
+    }
+
+    /// 手机号+验证码登录
+    ///
+    /// 登录成功后只更新 cookie ，之后需与二维码登录一样调用 `store_cookie()` 和 `check_login_status()`
+    pub async fn login_by_cellphone_captcha(&mut self, phone: &str, captcha: &str) -> Result<()> {
+        self.login_by_form("login/cellphone", &[("phone", phone), ("captcha", captcha)]).await
+    }
+
+    /// 邮箱+密码登录
+    ///
+    /// 登录成功后只更新 cookie ，之后需与二维码登录一样调用 `store_cookie()` 和 `check_login_status()`
+    pub async fn login_by_email(&mut self, email: &str, password: &str) -> Result<()> {
+        self.login_by_form("login", &[("email", email), ("password", password)]).await
+    }
+
+    /// 以表单提交登录信息，成功时更新 cookie
+    async fn login_by_form(&mut self, api_path: &str, form: &[(&str, &str)]) -> Result<()> {
+        let login_response = self
+            .http_client
+            .post(format!("{}/{}?timestamp={}", &self.api_url, api_path, Utc::now().timestamp()))
+            .form(form)
+            .send()
+            .await?;
+
+        let v_login: Value = serde_json::from_slice(&login_response.bytes().await?)?;
+
+        // 状态码报错（密码错误、验证码错误等）
+        self.check_response_code(&v_login, "login")?;
+
+        match v_login["cookie"].as_str() {
+            Some(cookie) if !cookie.is_empty() => {
+                self.cookie = cookie.to_string();
+                debug!("login by {} successfully", api_path);
+                Ok(())
+            },
+            _ => Err(NcmError::Decode(String::from("/cookie"))),
+        }
+    }
+
+    /// 获取登录状态，更新登录的账号信息（未登录时为 None）
     pub async fn check_login_status(&mut self) -> Result<()> {
         let status_response = self
             .http_client
@@ -286,12 +404,9 @@ impl NcmClient {
 
         let mut v: Value = serde_json::from_slice(&status_response)?;
         let v_profile = v["data"]["profile"].take();
-        if !v_profile.is_null() {
-            if let Ok(account) = Account::from_json(v_profile) {
-                debug!("login, {:?}", account);
-                self.login_account = Some(account);
-            }
-        }
+        // 无 profile 表示未登录（cookie 无效），profile 字段不全时返回解析错误，由调用方决定如何处理
+        self.login_account = if v_profile.is_null() { None } else { Some(Account::from_json(v_profile)?) };
+        debug!("login status: {:?}", self.login_account);
 
         Ok(())
     }
@@ -310,9 +425,36 @@ impl NcmClient {
         self.login_account.clone()
     }
 
-    /// 登出
+    /// 登出：通知上游登出，并清除本地 cookie 、账号信息和喜欢的歌曲缓存
+    ///
+    /// 上游登出失败（如网络异常）时仍会清除本地状态
     pub async fn logout(&mut self) -> Result<()> {
-        // TODO
+        if !self.cookie.is_empty() {
+            let logout_result = self
+                .http_client
+                .post(format!("{}/logout?timestamp={}", &self.api_url, Utc::now().timestamp()))
+                .form(&[("cookie", &self.cookie)])
+                .send()
+                .await;
+            if let Err(err) = logout_result {
+                error!("failed to logout from api: {:?}", err);
+            }
+        }
+
+        // 删除本地 cookie 文件
+        match fs::remove_file(&self.cookie_path) {
+            Ok(_) => debug!("cookie removed at {:?}", &self.cookie_path),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {},
+            Err(err) => error!("failed to remove cookie at {:?}: {}", &self.cookie_path, err),
+        }
+
+        self.cookie = String::new();
+        self.login_account = None;
+        self.liked_song_ids = HashSet::new();
+        self.liked_song_ids_version += 1;
+
+        debug!("logout");
+
         Ok(())
     }
 }
@@ -326,51 +468,75 @@ impl NcmClient {
     pub async fn get_user_all_songlists(&self) -> Result<Vec<Songlist>> {
         let mut songlists: Vec<Songlist> = Vec::new();
 
-        if let Some(login_account) = self.login_account.as_ref() {
-            let user_id = login_account.user_id;
+        loop {
+            let (page, has_more) = self.get_user_songlists(songlists.len(), USER_SONGLISTS_PAGE_SIZE).await?;
+            let page_is_empty = page.is_empty();
+            songlists.extend(page);
 
-            let playlist_response = self
-                .http_client
-                .post(format!("{}/user/playlist?uid={}", &self.api_url, user_id))
-                .form(&[("cookie", &self.cookie)])
-                .send()
-                .await?;
+            // 没有更多页，或上游返回空页（防止死循环）
+            if !has_more || page_is_empty {
+                break;
+            }
+        }
 
-            let v_playlist: Value = serde_json::from_slice(&playlist_response.bytes().await?)?;
+        Ok(songlists)
+    }
 
-            // 状态码报错
-            if v_playlist["code"].as_u64().unwrap() != 200 {
-                return Err(anyhow!("failed to load songs into songlist, code {}", v_playlist["code"].as_u64().unwrap()));
-            }
-            // 仍有更多页
-            if v_playlist["more"].as_bool().unwrap() {
-                // TODO: 增加 offset ，继续获取
-            }
+    /// 分页获取用户歌单（创建的+收藏的），同时返回是否还有下一页
+    ///
+    /// 未登录时返回 `NcmError::NotLoggedIn`
+    pub async fn get_user_songlists(&self, offset: usize, limit: usize) -> Result<(Vec<Songlist>, bool)> {
+        let Some(login_account) = self.login_account.as_ref() else {
+            return Err(NcmError::NotLoggedIn);
+        };
 
-            for playlist in v_playlist["playlist"].as_array().unwrap() {
-                songlists.push(Songlist {
-                    name: playlist["name"].as_str().unwrap().to_string(),
-                    id: playlist["id"].as_u64().unwrap(),
-                    songs_count: playlist["trackCount"].as_u64().unwrap_or(0) as usize,
-                    creator: if let Some(creator_nickname) = playlist["creator"]["nickname"].as_str() {
-                        creator_nickname.to_string()
-                    } else {
-                        String::new()
-                    },
-                    songs: Vec::new(),
-                });
-            }
+        let playlist_response = self
+            .http_client
+            .post(format!("{}/user/playlist?uid={}&offset={}&limit={}", &self.api_url, login_account.user_id, offset, limit))
+            .form(&[("cookie", &self.cookie)])
+            .send()
+            .await?;
 
-            debug!("songlists: {:?}", songlists);
-        }
+        let mut v_playlist: Value = serde_json::from_slice(&playlist_response.bytes().await?)?;
 
-        Ok(songlists)
+        // 状态码报错
+        self.check_response_code(&v_playlist, "get user songlists")?;
+
+        let songlists = parse_json_array(v_playlist["playlist"].take())?;
+        // 仍有更多页
+        let has_more = v_playlist["more"].as_bool().unwrap_or(false);
+
+        debug!("songlists (offset {}): {:?}", offset, songlists);
+
+        Ok((songlists, has_more))
     }
 
     /// 装载歌单内的所有歌曲
     pub async fn load_songlist_songs(&self, songlist: &mut Songlist) -> Result<()> {
+        // 每日推荐伪歌单
+        if songlist.is_daily_recommend() {
+            songlist.songs = self.get_daily_recommend_songs().await?;
+            songlist.songs_count = songlist.songs.len();
+            return Ok(());
+        }
+
         songlist.songs = Vec::new();
 
+        // 云盘伪歌单
+        if songlist.is_cloud_drive() {
+            loop {
+                let (songs, has_more) = self.get_cloud_songs(songlist.songs.len(), CLOUD_SONGS_PAGE_SIZE).await?;
+                let page_is_empty = songs.is_empty();
+                songlist.songs.extend(songs);
+
+                if !has_more || page_is_empty {
+                    break;
+                }
+            }
+            songlist.songs_count = songlist.songs.len();
+            return Ok(());
+        }
+
         let mut offset = 0;
 
         while songlist.songs.len() % 1000 == 0 {
@@ -383,32 +549,17 @@ impl NcmClient {
 
             offset += 1000;
 
-            let v_playlist_detail: Value = serde_json::from_slice(&playlist_detail_response.bytes().await?)?;
+            let mut v_playlist_detail: Value = serde_json::from_slice(&playlist_detail_response.bytes().await?)?;
 
             // 状态码报错
-            if v_playlist_detail["code"].as_u64().unwrap() != 200 {
-                return Err(anyhow!("failed to load songs into songlist, code {}", v_playlist_detail["code"].as_u64().unwrap()));
-            }
+            self.check_response_code(&v_playlist_detail, "load songs into songlist")?;
             // 获取到的歌曲列表为空
-            if v_playlist_detail["songs"].as_array().unwrap().is_empty() {
+            if v_playlist_detail["songs"].as_array().is_none_or(|songs| songs.is_empty()) {
                 break;
             }
 
             // 局部反序列化并装载
-            for track in v_playlist_detail["songs"].as_array().unwrap() {
-                let song = Song {
-                    name: track["name"].as_str().unwrap().to_string(),
-                    id: track["id"].as_u64().unwrap(),
-                    singer: track["ar"][0]["name"].as_str().unwrap_or("Unknown").to_string(),
-                    singer_id: track["ar"][0]["id"].as_u64().unwrap(),
-                    album: track["al"]["name"].as_str().unwrap_or("Unknown").to_string(),
-                    album_id: track["al"]["id"].as_u64().unwrap(),
-                    duration: track["dt"].as_u64().unwrap(),
-                    song_url: None,
-                    quality_level: String::new(),
-                };
-                songlist.songs.push(song);
-            }
+            songlist.songs.extend(parse_json_array::<Song>(v_playlist_detail["songs"].take())?);
         }
 
         debug!("{:?}", songlist.songs);
@@ -417,8 +568,532 @@ impl NcmClient {
     }
 }
 
+// 歌单编辑 api
+impl NcmClient {
+    /// 新建歌单，返回新歌单（不含歌曲）
+    pub async fn create_songlist(&self, name: &str) -> Result<Songlist> {
+        if !self.is_login() {
+            return Err(NcmError::NotLoggedIn);
+        }
+
+        let create_response = self
+            .http_client
+            .post(format!("{}/playlist/create?timestamp={}", &self.api_url, Utc::now().timestamp()))
+            .form(&[("cookie", self.cookie.as_str()), ("name", name)])
+            .send()
+            .await?;
+
+        let mut v_create: Value = serde_json::from_slice(&create_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_create, &format!("create songlist {}", name))?;
+
+        let mut songlist = Songlist::from_json(v_create["playlist"].take())?;
+        // 新建歌单的响应中没有创建者昵称
+        if songlist.creator.is_empty() {
+            if let Some(login_account) = self.login_account.as_ref() {
+                songlist.creator = login_account.nickname.clone();
+            }
+        }
+
+        debug!("songlist created: {:?}", songlist);
+
+        Ok(songlist)
+    }
+
+    /// 删除歌单
+    pub async fn delete_songlist(&self, songlist_id: u64) -> Result<()> {
+        if !self.is_login() {
+            return Err(NcmError::NotLoggedIn);
+        }
+
+        let delete_response = self
+            .http_client
+            .post(format!("{}/playlist/delete?id={}&timestamp={}", &self.api_url, songlist_id, Utc::now().timestamp()))
+            .form(&[("cookie", &self.cookie)])
+            .send()
+            .await?;
+
+        let v_delete: Value = serde_json::from_slice(&delete_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_delete, &format!("delete songlist {}", songlist_id))?;
+
+        Ok(())
+    }
+
+    /// 重命名歌单
+    pub async fn rename_songlist(&self, songlist_id: u64, name: &str) -> Result<()> {
+        if !self.is_login() {
+            return Err(NcmError::NotLoggedIn);
+        }
+
+        let rename_response = self
+            .http_client
+            .post(format!("{}/playlist/name/update?timestamp={}", &self.api_url, Utc::now().timestamp()))
+            .form(&[("cookie", self.cookie.as_str()), ("id", songlist_id.to_string().as_str()), ("name", name)])
+            .send()
+            .await?;
+
+        let v_rename: Value = serde_json::from_slice(&rename_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_rename, &format!("rename songlist {}", songlist_id))?;
+
+        Ok(())
+    }
+
+    /// 收藏（`subscribe` 为 true）或取消收藏歌单
+    pub async fn subscribe_songlist(&self, songlist_id: u64, subscribe: bool) -> Result<()> {
+        if !self.is_login() {
+            return Err(NcmError::NotLoggedIn);
+        }
+
+        let subscribe_response = self
+            .http_client
+            .post(format!("{}/playlist/subscribe?t={}&id={}&timestamp={}", &self.api_url, if subscribe { 1 } else { 2 }, songlist_id, Utc::now().timestamp()))
+            .form(&[("cookie", &self.cookie)])
+            .send()
+            .await?;
+
+        let v_subscribe: Value = serde_json::from_slice(&subscribe_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_subscribe, &format!("{} songlist {}", if subscribe { "subscribe" } else { "unsubscribe" }, songlist_id))?;
+
+        Ok(())
+    }
+
+    /// 向歌单添加歌曲（`add` 为 true）或从歌单删除歌曲
+    pub async fn update_songlist_tracks(&self, songlist_id: u64, song_ids: &[u64], add: bool) -> Result<()> {
+        if !self.is_login() {
+            return Err(NcmError::NotLoggedIn);
+        }
+
+        let tracks = song_ids.iter().map(|id| id.to_string()).collect::<Vec<String>>().join(",");
+        let tracks_response = self
+            .http_client
+            .post(format!(
+                "{}/playlist/tracks?op={}&pid={}&tracks={}&timestamp={}",
+                &self.api_url,
+                if add { "add" } else { "del" },
+                songlist_id,
+                tracks,
+                Utc::now().timestamp()
+            ))
+            .form(&[("cookie", &self.cookie)])
+            .send()
+            .await?;
+
+        let v_tracks: Value = serde_json::from_slice(&tracks_response.bytes().await?)?;
+
+        // 状态码报错（该接口的上游状态码可能包在 body 中）
+        let v_code = if v_tracks["body"].is_object() { &v_tracks["body"] } else { &v_tracks };
+        self.check_response_code(v_code, &format!("{} tracks of songlist {}", if add { "add" } else { "remove" }, songlist_id))?;
+
+        Ok(())
+    }
+}
+
+// 推荐 api
+impl NcmClient {
+    /// 获取每日推荐歌曲（包括推荐理由）
+    pub async fn get_daily_recommend_songs(&self) -> Result<Vec<Song>> {
+        if !self.is_login() {
+            return Err(NcmError::NotLoggedIn);
+        }
+
+        let recommend_response = self
+            .http_client
+            .post(format!("{}/recommend/songs?timestamp={}", &self.api_url, Utc::now().timestamp()))
+            .form(&[("cookie", &self.cookie)])
+            .send()
+            .await?;
+
+        let mut v_recommend: Value = serde_json::from_slice(&recommend_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_recommend, "get daily recommend songs")?;
+
+        // 推荐理由单独返回，按歌曲 id 对应
+        let reasons: HashMap<u64, String> = v_recommend["data"]["recommendReasons"]
+            .as_array()
+            .map(|reasons| {
+                reasons
+                    .iter()
+                    .filter_map(|reason| Some((reason["songId"].as_u64()?, reason["reason"].as_str()?.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut songs = parse_json_array::<Song>(v_recommend["data"]["dailySongs"].take())?;
+        for song in songs.iter_mut() {
+            song.recommend_reason = reasons.get(&song.id).cloned();
+        }
+
+        debug!("daily recommend songs: {:?}", songs);
+
+        Ok(songs)
+    }
+
+    /// 获取私人FM的下一批歌曲（每次约 3 首）
+    pub async fn get_personal_fm(&self) -> Result<Vec<Song>> {
+        if !self.is_login() {
+            return Err(NcmError::NotLoggedIn);
+        }
+
+        let fm_response = self
+            .http_client
+            .post(format!("{}/personal_fm?timestamp={}", &self.api_url, Utc::now().timestamp()))
+            .form(&[("cookie", &self.cookie)])
+            .send()
+            .await?;
+
+        let mut v_fm: Value = serde_json::from_slice(&fm_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_fm, "get personal fm")?;
+
+        let songs = parse_json_array::<Song>(v_fm["data"].take())?;
+
+        debug!("personal fm songs: {:?}", songs);
+
+        Ok(songs)
+    }
+
+    /// 获取心动模式歌曲列表，以 `song_id` 为种子、`songlist_id` 为所在歌单（一般为“我喜欢的音乐”）
+    ///
+    /// 返回的歌曲中混有歌单内的歌曲，推荐歌曲的 `is_recommended` 为 true
+    pub async fn get_intelligence_list(&self, song_id: u64, songlist_id: u64) -> Result<Vec<Song>> {
+        if !self.is_login() {
+            return Err(NcmError::NotLoggedIn);
+        }
+
+        let intelligence_response = self
+            .http_client
+            .post(format!("{}/playmode/intelligence/list?id={}&pid={}&timestamp={}", &self.api_url, song_id, songlist_id, Utc::now().timestamp()))
+            .form(&[("cookie", &self.cookie)])
+            .send()
+            .await?;
+
+        let mut v_intelligence: Value = serde_json::from_slice(&intelligence_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_intelligence, "get intelligence list")?;
+
+        let mut songs = Vec::new();
+        if let Some(items) = v_intelligence["data"].as_array_mut() {
+            for item in items.iter_mut() {
+                let mut song = Song::from_json(item["songInfo"].take())?;
+                song.is_recommended = item["recommended"].as_bool().unwrap_or(false);
+                songs.push(song);
+            }
+        }
+
+        debug!("intelligence list: {:?}", songs);
+
+        Ok(songs)
+    }
+
+    /// 将歌曲移出私人FM（“不喜欢”），之后的推荐会参考该操作
+    pub async fn fm_trash(&self, song_id: u64) -> Result<()> {
+        if !self.is_login() {
+            return Err(NcmError::NotLoggedIn);
+        }
+
+        let trash_response = self
+            .http_client
+            .post(format!("{}/fm_trash?id={}&timestamp={}", &self.api_url, song_id, Utc::now().timestamp()))
+            .form(&[("cookie", &self.cookie)])
+            .send()
+            .await?;
+
+        let v_trash: Value = serde_json::from_slice(&trash_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_trash, "move song out of personal fm")?;
+
+        Ok(())
+    }
+
+    /// 获取与歌曲相似的歌曲
+    pub async fn get_similar_songs(&self, song_id: u64) -> Result<Vec<Song>> {
+        let similar_response = self
+            .http_client
+            .post(format!("{}/simi/song?id={}", &self.api_url, song_id))
+            .form(&[("cookie", &self.cookie)])
+            .send()
+            .await?;
+
+        let mut v_similar: Value = serde_json::from_slice(&similar_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_similar, &format!("get similar songs of song {}", song_id))?;
+
+        parse_json_array(v_similar["songs"].take())
+    }
+
+    /// 获取包含该歌曲的相似歌单（缩略，不含歌曲）
+    pub async fn get_similar_songlists(&self, song_id: u64) -> Result<Vec<Songlist>> {
+        let similar_response = self
+            .http_client
+            .post(format!("{}/simi/playlist?id={}", &self.api_url, song_id))
+            .form(&[("cookie", &self.cookie)])
+            .send()
+            .await?;
+
+        let mut v_similar: Value = serde_json::from_slice(&similar_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_similar, &format!("get similar songlists of song {}", song_id))?;
+
+        parse_json_array(v_similar["playlists"].take())
+    }
+}
+
+// 专辑 api
+impl NcmClient {
+    /// 获取专辑详情（包括专辑内的所有歌曲）
+    pub async fn get_album(&self, album_id: u64) -> Result<Album> {
+        let album_response = self
+            .http_client
+            .post(format!("{}/album?id={}", &self.api_url, album_id))
+            .form(&[("cookie", &self.cookie)])
+            .send()
+            .await?;
+
+        let mut v_album: Value = serde_json::from_slice(&album_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_album, &format!("get album {}", album_id))?;
+
+        let mut album = Album::from_json(v_album["album"].take())?;
+        album.songs = parse_json_array(v_album["songs"].take())?;
+
+        debug!("album: {:?}", album);
+
+        Ok(album)
+    }
+}
+
+// 歌手 api
+impl NcmClient {
+    /// 获取歌手详情
+    pub async fn get_artist_detail(&self, artist_id: u64) -> Result<Artist> {
+        let artist_response = self
+            .http_client
+            .post(format!("{}/artist/detail?id={}", &self.api_url, artist_id))
+            .form(&[("cookie", &self.cookie)])
+            .send()
+            .await?;
+
+        let mut v_artist: Value = serde_json::from_slice(&artist_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_artist, &format!("get artist {}", artist_id))?;
+
+        let artist = Artist::from_json(v_artist["data"]["artist"].take())?;
+
+        debug!("artist: {:?}", artist);
+
+        Ok(artist)
+    }
+
+    /// 获取歌手的热门 50 首歌曲
+    pub async fn get_artist_top_songs(&self, artist_id: u64) -> Result<Vec<Song>> {
+        let top_songs_response = self
+            .http_client
+            .post(format!("{}/artist/top/song?id={}", &self.api_url, artist_id))
+            .form(&[("cookie", &self.cookie)])
+            .send()
+            .await?;
+
+        let mut v_top_songs: Value = serde_json::from_slice(&top_songs_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_top_songs, "get artist top songs")?;
+
+        let songs = parse_json_array(v_top_songs["songs"].take())?;
+
+        debug!("artist {} top songs: {:?}", artist_id, songs);
+
+        Ok(songs)
+    }
+
+    /// 获取歌手的专辑（分页，按发行时间倒序），同时返回是否还有下一页
+    pub async fn get_artist_albums(&self, artist_id: u64, offset: usize, limit: usize) -> Result<(Vec<Album>, bool)> {
+        let albums_response = self
+            .http_client
+            .post(format!("{}/artist/album?id={}&offset={}&limit={}", &self.api_url, artist_id, offset, limit))
+            .form(&[("cookie", &self.cookie)])
+            .send()
+            .await?;
+
+        let mut v_albums: Value = serde_json::from_slice(&albums_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_albums, "get artist albums")?;
+
+        let albums = parse_json_array(v_albums["hotAlbums"].take())?;
+        let has_more = v_albums["more"].as_bool().unwrap_or(false);
+
+        debug!("artist {} albums (offset {}): {:?}", artist_id, offset, albums);
+
+        Ok((albums, has_more))
+    }
+
+    /// 获取相似歌手（需要登录）
+    pub async fn get_similar_artists(&self, artist_id: u64) -> Result<Vec<Artist>> {
+        let similar_artists_response = self
+            .http_client
+            .post(format!("{}/simi/artist?id={}", &self.api_url, artist_id))
+            .form(&[("cookie", &self.cookie)])
+            .send()
+            .await?;
+
+        let mut v_similar_artists: Value = serde_json::from_slice(&similar_artists_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_similar_artists, "get similar artists")?;
+
+        let artists = parse_json_array(v_similar_artists["artists"].take())?;
+
+        debug!("artist {} similar artists: {:?}", artist_id, artists);
+
+        Ok(artists)
+    }
+}
+
+// 搜索 api
+impl NcmClient {
+    /// 搜索（分页）
+    pub async fn search(&self, keywords: &str, search_type: SearchType, offset: usize, limit: usize) -> Result<SearchResult> {
+        let search_response = self
+            .http_client
+            .post(format!("{}/cloudsearch", &self.api_url))
+            .query(&[("keywords", keywords)])
+            .query(&[("type", search_type.code()), ("offset", offset), ("limit", limit)])
+            .form(&[("cookie", &self.cookie)])
+            .send()
+            .await?;
+
+        let mut v_search: Value = serde_json::from_slice(&search_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_search, &format!("search {}", search_type))?;
+
+        let mut v_result = v_search["result"].take();
+        let search_result = match search_type {
+            SearchType::Song => SearchResult::Songs(parse_json_array(v_result["songs"].take())?),
+            SearchType::Album => SearchResult::Albums(parse_json_array(v_result["albums"].take())?),
+            SearchType::Artist => SearchResult::Artists(parse_json_array(v_result["artists"].take())?),
+            SearchType::Songlist => SearchResult::Songlists(parse_json_array(v_result["playlists"].take())?),
+            SearchType::Lyric => {
+                let mut lyrics = Vec::new();
+                if let Value::Array(tracks) = v_result["songs"].take() {
+                    for mut track in tracks {
+                        // 匹配到的歌词片段，取第一个非空行（新版接口为 `{ txt, range }`，旧版为字符串数组）
+                        let matched_lyric = match track["lyrics"].take() {
+                            Value::Object(lyrics) => lyrics["txt"].as_str().and_then(|txt| txt.lines().find(|line| !line.trim().is_empty())).unwrap_or("").to_string(),
+                            Value::Array(lines) => lines.iter().filter_map(|line| line.as_str()).find(|line| !line.trim().is_empty()).unwrap_or("").to_string(),
+                            _ => String::new(),
+                        };
+                        lyrics.push((Song::from_json(track)?, matched_lyric));
+                    }
+                }
+                SearchResult::Lyrics(lyrics)
+            },
+        };
+
+        debug!("search {} `{}` (offset {}): {:?}", search_type, keywords, offset, search_result);
+
+        Ok(search_result)
+    }
+
+    /// 搜索歌曲（分页）
+    pub async fn search_songs(&self, keywords: &str, offset: usize, limit: usize) -> Result<Vec<Song>> {
+        match self.search(keywords, SearchType::Song, offset, limit).await? {
+            SearchResult::Songs(songs) => Ok(songs),
+            _ => Ok(Vec::new()),
+        }
+    }
+}
+
 // 歌曲 api
 impl NcmClient {
+    /// 获取用户喜欢的所有歌曲 id ，并更新本地缓存
+    pub async fn liked_song_ids(&mut self) -> Result<HashSet<u64>> {
+        let Some(login_account) = self.login_account.as_ref() else {
+            return Err(NcmError::NotLoggedIn);
+        };
+
+        let likelist_response = self
+            .http_client
+            .post(format!("{}/likelist?uid={}&timestamp={}", &self.api_url, login_account.user_id, Utc::now().timestamp()))
+            .form(&[("cookie", &self.cookie)])
+            .send()
+            .await?;
+
+        let v_likelist: Value = serde_json::from_slice(&likelist_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_likelist, "get liked songs")?;
+
+        if let Some(ids) = v_likelist["ids"].as_array() {
+            self.liked_song_ids = ids.iter().filter_map(|id| id.as_u64()).collect();
+            self.liked_song_ids_version += 1;
+        }
+
+        debug!("liked {} songs", self.liked_song_ids.len());
+
+        Ok(self.liked_song_ids.clone())
+    }
+
+    /// 缓存中用户喜欢的所有歌曲 id
+    pub fn cached_liked_song_ids(&self) -> &HashSet<u64> {
+        &self.liked_song_ids
+    }
+
+    /// 喜欢的歌曲缓存的版本，缓存每次变化时递增
+    pub fn liked_song_ids_version(&self) -> usize {
+        self.liked_song_ids_version
+    }
+
+    /// 歌曲是否被喜欢（根据本地缓存判断）
+    pub fn is_liked_song(&self, song_id: u64) -> bool {
+        self.liked_song_ids.contains(&song_id)
+    }
+
+    /// 喜欢/取消喜欢歌曲
+    pub async fn like_song(&mut self, song_id: u64, like: bool) -> Result<()> {
+        if !self.is_login() {
+            return Err(NcmError::NotLoggedIn);
+        }
+
+        let like_response = self
+            .http_client
+            .post(format!("{}/like?id={}&like={}&timestamp={}", &self.api_url, song_id, like, Utc::now().timestamp()))
+            .form(&[("cookie", &self.cookie)])
+            .send()
+            .await?;
+
+        let v_like: Value = serde_json::from_slice(&like_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_like, &format!("{} song {}", if like { "like" } else { "unlike" }, song_id))?;
+
+        if like {
+            self.liked_song_ids.insert(song_id);
+        } else {
+            self.liked_song_ids.remove(&song_id);
+        }
+        self.liked_song_ids_version += 1;
+
+        debug!("{} song {}", if like { "like" } else { "unlike" }, song_id);
+
+        Ok(())
+    }
+
     /// 检查歌曲是否可获取
     pub async fn check_song_availability(&self, song_id: u64) -> Result<bool> {
         let check_response = self
@@ -430,47 +1105,150 @@ impl NcmClient {
 
         let v_check_response: Value = serde_json::from_slice(&check_response.bytes().await?)?;
 
-        if v_check_response["code"].as_u64().unwrap() == 200 {
+        if v_check_response["code"].as_u64() == Some(200) {
             return Ok(v_check_response["success"].as_bool().unwrap_or(false));
         }
 
         Ok(false)
     }
 
-    /// 装载歌曲 url
+    /// 装载歌曲 url，优先获取设置中的音质，不可用时依次回退到更低的音质
     pub async fn load_song_url(&self, song: &mut Song) -> Result<()> {
         song.song_url = None;
+        song.quality_level = None;
 
-        let song_url_response = self
-            .http_client
-            .post(format!("{}/song/url/v1?id={}&level={}", &self.api_url, song.id, "jymaster"))
-            .form(&[("cookie", &self.cookie)])
-            .send()
-            .await?;
+        let mut requested_level = Some(self.settings.preferred_quality);
+        while let Some(level) = requested_level {
+            let song_url_response = self
+                .http_client
+                .post(format!("{}/song/url/v1?id={}&level={}", &self.api_url, song.id, level.as_str()))
+                .form(&[("cookie", &self.cookie)])
+                .send()
+                .await?;
 
-        let v_song_url: Value = serde_json::from_slice(&song_url_response.bytes().await?)?;
-
-        if let Some(song_url) = v_song_url["data"][0]["url"].as_str() {
-            song.song_url = Some(song_url.to_string());
-        }
-        if let Some(quality_level) = v_song_url["data"][0]["level"].as_str() {
-            song.quality_level = match quality_level {
-                "standard" => String::from("标准"),
-                "higher" => String::from("较高"),
-                "exhigh" => String::from("极高"),
-                "lossless" => String::from("无损"),
-                "hires" => String::from("Hi-Res"),
-                "jyeffect" => String::from("高清环绕声"),
-                "sky" => String::from("沉浸环绕声"),
-                "dolby" => String::from("杜比全景声"),
-                "jymaster" => String::from("超清母带"),
-                _ => quality_level.to_string(),
-            };
+            let v_song_url: Value = serde_json::from_slice(&song_url_response.bytes().await?)?;
+
+            // 状态码报错
+            self.check_response_code(&v_song_url, &format!("get url of song {}", song.id))?;
+
+            if let Some(song_url) = v_song_url["data"][0]["url"].as_str() {
+                song.song_url = Some(song_url.to_string());
+                // 接口可能返回比请求更低的音质，以返回的为准
+                song.quality_level = v_song_url["data"][0]["level"].as_str().and_then(QualityLevel::from_level).or(Some(level));
+                break;
+            }
+
+            debug!("quality level {} of song {} is unavailable, fallback", level.as_str(), song.id);
+            requested_level = level.fallback();
         }
 
         Ok(())
     }
 
+    /// 上报听歌记录（计入云端记录和听歌报告），`sourceid` 为来源歌单 id
+    pub async fn scrobble(&self, song_id: u64, sourceid: u64, played_seconds: u64) -> Result<()> {
+        if !self.is_login() {
+            return Err(NcmError::NotLoggedIn);
+        }
+
+        self.scrobble_queue().send(song_id, sourceid, played_seconds).await
+    }
+
+    /// 是否开启听歌记录上报
+    pub fn is_scrobble_enabled(&self) -> bool {
+        self.settings.enable_scrobble
+    }
+
+    /// 优先获取的音质
+    pub fn preferred_quality(&self) -> QualityLevel {
+        self.settings.preferred_quality
+    }
+
+    /// 设置优先获取的音质并保存到设置文件，从下一次装载歌曲 url 起生效
+    pub fn set_preferred_quality(&mut self, quality_level: QualityLevel) {
+        self.settings.preferred_quality = quality_level;
+        self.store_settings();
+    }
+
+    /// 上报听歌记录，网络异常时保存到本地队列，下次启动后由 `retry_pending_scrobbles()` 重试
+    ///
+    /// 其他错误（如上游拒绝该记录）重试也不会成功，直接丢弃。返回的 future 不借用 `self` ，上报时无需持有 `NcmClient` 的锁
+    pub fn scrobble_or_enqueue(&self, song_id: u64, sourceid: u64, played_seconds: u64) -> impl Future<Output = ()> + Send + 'static {
+        let scrobble_queue = self.scrobble_queue();
+        let record = self.scrobble_user_id().map(|user_id| ScrobbleRecord {
+            user_id,
+            song_id,
+            sourceid,
+            played_seconds,
+        });
+
+        async move {
+            let Some(record) = record else {
+                return;
+            };
+
+            match scrobble_queue.send(record.song_id, record.sourceid, record.played_seconds).await {
+                Ok(_) => {},
+                Err(err @ NcmError::Network(_)) => {
+                    error!("failed to scrobble song {}, enqueue it: {:?}", record.song_id, err);
+                    scrobble_queue.push_back(vec![record]).await;
+                },
+                Err(err) => error!("failed to scrobble song {}, drop it: {:?}", record.song_id, err),
+            }
+        }
+    }
+
+    /// 重试本地队列中当前账号上报失败的听歌记录，仍因网络异常失败的和其他账号的记录保留在队列中
+    ///
+    /// 与 `scrobble_or_enqueue()` 一样，返回的 future 不借用 `self`
+    pub fn retry_pending_scrobbles(&self) -> impl Future<Output = ()> + Send + 'static {
+        let scrobble_queue = self.scrobble_queue();
+        let user_id = self.scrobble_user_id();
+
+        async move {
+            let Some(user_id) = user_id else {
+                return;
+            };
+
+            // 先取出当前账号的记录，重试期间新上报失败的记录仍可入队
+            let records = scrobble_queue.take_user_records(user_id).await;
+            if records.is_empty() {
+                return;
+            }
+
+            let mut failed_records = Vec::new();
+            for record in records {
+                match scrobble_queue.send(record.song_id, record.sourceid, record.played_seconds).await {
+                    Ok(_) => {},
+                    Err(err @ NcmError::Network(_)) => {
+                        error!("failed to retry scrobbling song {}: {:?}", record.song_id, err);
+                        failed_records.push(record);
+                    },
+                    Err(err) => error!("failed to retry scrobbling song {}, drop it: {:?}", record.song_id, err),
+                }
+            }
+
+            debug!("{} scrobble records failed again", failed_records.len());
+            scrobble_queue.push_front(failed_records).await;
+        }
+    }
+
+    /// 开启上报且已登录时，返回听歌记录所属的用户 id
+    fn scrobble_user_id(&self) -> Option<u64> {
+        self.login_account.as_ref().filter(|_| self.is_scrobble_enabled()).map(|account| account.user_id)
+    }
+
+    fn scrobble_queue(&self) -> ScrobbleQueue {
+        ScrobbleQueue {
+            http_client: self.http_client.clone(),
+            api_url: self.api_url.clone(),
+            cookie: self.cookie.clone(),
+            path: self.scrobble_queue_path.clone(),
+            lock: self.scrobble_queue_lock.clone(),
+        }
+    }
+
+
     /// 获取歌曲的歌词
     pub async fn get_song_lyrics(&self, song_id: u64) -> Result<Lyrics> {
         // 优先尝试从本地缓存读取歌词
@@ -487,16 +1265,16 @@ impl NcmClient {
 
         let v_lyric: Value = serde_json::from_slice(&lyric_response.bytes().await?)?;
 
-        let lyric_text = v_lyric["lrc"]["lyric"].as_str().unwrap_or("").to_string();
-        let trans_lyric_text = v_lyric["tlyric"]["lyric"].as_str().unwrap_or("").to_string();
-        let roman_lyric_text = v_lyric["romalrc"]["lyric"].as_str().unwrap_or("").to_string();
+        // 状态码报错
+        self.check_response_code(&v_lyric, &format!("get lyrics of song {}", song_id))?;
 
-        let origin_lyric_lines: Vec<String> = lyric_text.split('\n').into_iter().map(|s| s.to_string()).collect();
-        let origin_trans_lyric_lines: Vec<String> = trans_lyric_text.split('\n').into_iter().map(|s| s.to_string()).collect();
-        let origin_roman_lyric_lines: Vec<String> = roman_lyric_text.split('\n').into_iter().map(|s| s.to_string()).collect();
+        let lyric_text = v_lyric["lrc"]["lyric"].as_str().unwrap_or("");
+        let trans_lyric_text = v_lyric["tlyric"]["lyric"].as_str().unwrap_or("");
+        let roman_lyric_text = v_lyric["romalrc"]["lyric"].as_str().unwrap_or("");
+        let yrc_lyric_text = v_lyric["yrc"]["lyric"].as_str().unwrap_or("");
 
         // 编码歌词
-        let lyrics = encode_lyrics(origin_lyric_lines, origin_trans_lyric_lines, origin_roman_lyric_lines);
+        let lyrics = lrc::encode_lyrics(lyric_text, trans_lyric_text, roman_lyric_text, yrc_lyric_text);
 
         debug!("lyrics encoded: {:?}", lyrics);
 
@@ -508,12 +1286,15 @@ impl NcmClient {
 
     /// 缓存歌词
     fn store_lyrics_cache(&self, song_id: u64, lyrics: &Lyrics) {
+        // 删除旧格式（无版本号）的缓存
+        let _ = fs::remove_file(self.lyrics_path.clone().join(format!("{}.lyrics", song_id)));
+
         match serde_json::to_string(lyrics) {
             Ok(lyrics_json) => match fs::OpenOptions::new()
                 .write(true)
                 .create(true)
                 .truncate(true)
-                .open(self.lyrics_path.clone().join(format!("{}.lyrics", song_id)))
+                .open(self.lyrics_cache_path(song_id))
             {
                 Ok(mut lyrics_file) => match lyrics_file.write_all(lyrics_json.as_bytes()) {
                     Ok(_) => debug!("lyrics stored at {:?}", &self.lyrics_path),
@@ -527,9 +1308,28 @@ impl NcmClient {
         }
     }
 
+    /// 清除本地的所有歌词缓存
+    pub fn clear_lyrics_cache(&self) -> Result<()> {
+        for entry in fs::read_dir(&self.lyrics_path)? {
+            let lyrics_file_path = entry?.path();
+            if lyrics_file_path.extension().is_some_and(|ext| ext == "lyrics") {
+                fs::remove_file(lyrics_file_path)?;
+            }
+        }
+
+        debug!("lyrics cache cleared at {:?}", &self.lyrics_path);
+
+        Ok(())
+    }
+
+    /// 歌词缓存文件路径，文件名带有缓存格式版本（如 `123.v2.lyrics`），旧版本的缓存不会被读取
+    fn lyrics_cache_path(&self, song_id: u64) -> PathBuf {
+        self.lyrics_path.clone().join(format!("{}.v{}.lyrics", song_id, LYRICS_CACHE_VERSION))
+    }
+
     /// 尝试读本地歌词缓存
     fn try_read_lyrics_cache(&self, song_id: u64) -> Result<Lyrics> {
-        let mut lyrics_file = File::open(self.lyrics_path.clone().join(format!("{}.lyrics", song_id)))?;
+        let mut lyrics_file = File::open(self.lyrics_cache_path(song_id))?;
         let mut json_data = String::new();
         lyrics_file.read_to_string(&mut json_data)?;
         let lyrics: Lyrics = serde_json::from_str(&json_data)?;
@@ -537,99 +1337,314 @@ impl NcmClient {
 
         Ok(lyrics)
     }
+
+    /// 获取封面图片（原始的 jpg/png 数据），优先从本地缓存读取
+    ///
+    /// 返回的 future 不借用 `self`，调用方可以释放 `NcmClient` 后再等待下载完成
+    pub fn get_cover_image(&self, cover_url: &str) -> impl Future<Output = Result<Vec<u8>>> + Send + 'static {
+        // 以链接中的文件名作为缓存名（如 `109951165345.jpg`）
+        let cover_file_name: String = cover_url.rsplit('/').next().unwrap_or(cover_url).chars().filter(|c| c.is_ascii_alphanumeric() || *c == '.' || *c == '-' || *c == '_').collect();
+        let cover_file_path = self.covers_path.clone().join(&cover_file_name);
+        let cover_url = cover_url.to_string();
+        let http_client = self.http_client.clone();
+
+        async move {
+            if let Ok(cover) = tokio::fs::read(&cover_file_path).await {
+                return Ok(cover);
+            }
+
+            // 只需缩略图，由服务端缩放，减小下载量
+            let cover_response = http_client.get(format!("{}?param={}y{}", cover_url, COVER_IMAGE_SIZE, COVER_IMAGE_SIZE)).send().await?;
+            if !cover_response.status().is_success() {
+                return Err(NcmError::ApiCode(cover_response.status().as_u16() as i64, format!("failed to download cover {}", cover_url)));
+            }
+            let cover = cover_response.bytes().await?.to_vec();
+
+            // 将封面缓存到本地
+            match tokio::fs::write(&cover_file_path, &cover).await {
+                Ok(_) => debug!("cover stored at {:?}", &cover_file_path),
+                Err(err) => error!("failed to store cover at {:?}: {:?}", &cover_file_path, err),
+            }
+
+            Ok(cover)
+        }
+    }
 }
 
-#[inline]
-/// 编码并序列化歌词
-fn encode_lyrics(origin_lyric_lines: Vec<String>, origin_trans_lyric_lines: Vec<String>, origin_roman_lyric_lines: Vec<String>) -> Lyrics {
-    let mut lyrics: Lyrics = Vec::new();
-
-    // 正则表达式
-    let timestamp_re = Regex::new(r"\[\d+:\d+.\d+]").unwrap(); // 时间戳
-    let timestamp_abnormal_re = Regex::new(r"^\[(\d+):(\d+):(\d+)]").unwrap(); // 不正常时间戳
-    let timestamp_9bit_re = Regex::new(r"\[(\d+):(\d+).(\d)]").unwrap(); // 9位时间戳（小数点后ms部分只有1位）
-    let timestamp_10bit_re = Regex::new(r"\[(\d+):(\d+).(\d)(\d)]").unwrap(); // 10位时间戳（小数点后ms部分只有2位）
-    let timestamp_7bit_re = Regex::new(r"\[(\d+):(\d+)]").unwrap(); // 7位时间戳（无小数点及ms部分）
-
-    // 修正闭包
-    let fix_line = |line: &String| -> String {
-        let mut fixed = timestamp_7bit_re.replace_all(line, "[$1:$2.000]").to_string();
-        fixed = timestamp_10bit_re.replace_all(&fixed, "[$1:$2.0$3$4]").to_string();
-        fixed = timestamp_9bit_re.replace_all(&fixed, "[$1:$2.00$3]").to_string();
-        fixed = timestamp_abnormal_re.replace_all(&fixed, "[$1:$2.$3]").to_string();
-        fixed.to_string()
-    };
-
-    // 进行修正
-    let fixed_lyric_lines: Vec<String> = origin_lyric_lines.iter().map(fix_line).collect();
-    let fixed_trans_lyric_lines: Vec<String> = origin_trans_lyric_lines.iter().map(fix_line).collect();
-    let fixed_roman_lyric_lines: Vec<String> = origin_roman_lyric_lines.iter().map(fix_line).collect();
-
-    // 匹配时间戳并编码
-    let mut trans_lyric_line_pointer = (fixed_trans_lyric_lines.len() - 1) as isize;
-    let mut roman_lyric_line_pointer = (fixed_roman_lyric_lines.len() - 1) as isize;
-    //
-    for lyric_line in fixed_lyric_lines.iter().rev() {
-        // lyric
-        if timestamp_re.is_match(lyric_line) {
-            // 计算时间戳
-            let timestamp = (lyric_line[1..=2].parse::<u64>().unwrap() * 60 + lyric_line[4..=5].parse::<u64>().unwrap()) * 1000 + lyric_line[7..=9].parse::<u64>().unwrap_or(0);
-
-            lyrics.push(LyricLine {
-                timestamp,
-                lyric_line: timestamp_re.replace_all(lyric_line, "").trim_end_matches('\t').to_string(),
-                trans_lyric_line: None,
-                roman_lyric_line: None,
-            })
-        } else {
-            continue;
+// 评论 api
+impl NcmClient {
+    /// 分页获取歌曲评论，`cursor` 为上一页返回的 `next_cursor`，获取第一页时为 None
+    ///
+    /// 按时间排序时游标为上一页最后一条评论的时间，其余排序方式游标为页码
+    pub async fn get_song_comments(&self, song_id: u64, sort: CommentSortType, cursor: Option<&str>) -> Result<CommentPage> {
+        let page_no: usize = match sort {
+            CommentSortType::Time => 1,
+            _ => cursor.and_then(|cursor| cursor.parse().ok()).unwrap_or(1),
+        };
+        let mut url = format!("{}/comment/new?type=0&id={}&sortType={}&pageNo={}&pageSize={}", &self.api_url, song_id, sort.code(), page_no, SONG_COMMENTS_PAGE_SIZE);
+        if let (CommentSortType::Time, Some(cursor)) = (sort, cursor) {
+            url.push_str(&format!("&cursor={}", cursor));
         }
 
-        // trans_lyric
-        while trans_lyric_line_pointer >= 0 {
-            if let Some(trans_lyric_line) = fixed_trans_lyric_lines.get(trans_lyric_line_pointer as usize) {
-                if !timestamp_re.is_match(trans_lyric_line) {
-                    trans_lyric_line_pointer -= 1;
-                    continue;
-                }
+        let comments_response = self.http_client.post(url).form(&[("cookie", &self.cookie)]).send().await?;
 
-                if trans_lyric_line.starts_with(&lyric_line[0..=10]) {
-                    if let Some(last) = lyrics.last_mut() {
-                        last.trans_lyric_line = Some(timestamp_re.replace_all(trans_lyric_line, "").trim_end_matches('\t').to_string());
-                    }
+        let mut v_comments: Value = serde_json::from_slice(&comments_response.bytes().await?)?;
 
-                    trans_lyric_line_pointer -= 1;
-                }
+        // 状态码报错
+        self.check_response_code(&v_comments, &format!("get comments of song {}", song_id))?;
 
-                break;
-            } else {
-                break;
+        let comments: Vec<Comment> = parse_json_array(v_comments["data"]["comments"].take())?;
+        let has_more = v_comments["data"]["hasMore"].as_bool().unwrap_or(false);
+        let next_cursor = match sort {
+            _ if !has_more || comments.is_empty() => None,
+            CommentSortType::Time => Some(v_comments["data"]["cursor"].as_str().map(|cursor| cursor.to_string()).unwrap_or_else(|| comments[comments.len() - 1].time.to_string())),
+            _ => Some((page_no + 1).to_string()),
+        };
+
+        Ok(CommentPage {
+            comments,
+            total_count: v_comments["data"]["totalCount"].as_u64().unwrap_or(0),
+            next_cursor,
+        })
+    }
+
+    /// 评论歌曲，`reply_to` 为被回复的评论 id，发表新评论时为 None
+    pub async fn post_song_comment(&self, song_id: u64, content: &str, reply_to: Option<u64>) -> Result<()> {
+        if !self.is_login() {
+            return Err(NcmError::NotLoggedIn);
+        }
+
+        let mut url = format!("{}/comment?t={}&type=0&id={}&timestamp={}", &self.api_url, if reply_to.is_some() { 2 } else { 1 }, song_id, Utc::now().timestamp());
+        if let Some(comment_id) = reply_to {
+            url.push_str(&format!("&commentId={}", comment_id));
+        }
+
+        let comment_response = self.http_client.post(url).form(&[("cookie", self.cookie.as_str()), ("content", content)]).send().await?;
+
+        let v_comment: Value = serde_json::from_slice(&comment_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_comment, &format!("comment on song {}", song_id))?;
+
+        Ok(())
+    }
+}
+
+// 云盘 api
+impl NcmClient {
+    /// 分页获取云盘歌曲，同时返回是否还有下一页
+    pub async fn get_cloud_songs(&self, offset: usize, limit: usize) -> Result<(Vec<Song>, bool)> {
+        if !self.is_login() {
+            return Err(NcmError::NotLoggedIn);
+        }
+
+        let cloud_response = self.http_client.post(format!("{}/user/cloud?offset={}&limit={}", &self.api_url, offset, limit)).form(&[("cookie", &self.cookie)]).send().await?;
+
+        let mut v_cloud: Value = serde_json::from_slice(&cloud_response.bytes().await?)?;
+
+        // 状态码报错
+        self.check_response_code(&v_cloud, "get cloud songs")?;
+
+        let mut songs = Vec::new();
+        if let Value::Array(values) = v_cloud["data"].take() {
+            for value in values {
+                songs.push(Song::from_cloud_json(value)?);
             }
         }
+        let has_more = v_cloud["hasMore"].as_bool().unwrap_or(false);
 
-        // roman_lyric
-        while roman_lyric_line_pointer >= 0 {
-            if let Some(roman_lyric_line) = fixed_roman_lyric_lines.get(roman_lyric_line_pointer as usize) {
-                if !timestamp_re.is_match(roman_lyric_line) {
-                    roman_lyric_line_pointer -= 1;
-                    continue;
-                }
+        debug!("cloud songs (offset {}): {:?}", offset, songs);
 
-                if roman_lyric_line.starts_with(&lyric_line[0..=10]) {
-                    if let Some(last) = lyrics.last_mut() {
-                        last.roman_lyric_line = Some(timestamp_re.replace_all(roman_lyric_line, "").trim_end_matches('\t').to_string());
-                    }
+        Ok((songs, has_more))
+    }
 
-                    roman_lyric_line_pointer -= 1;
-                }
+    /// 上传本地音频文件到云盘，返回上传进度和执行上传的 future
+    ///
+    /// 返回的 future 不借用 `self`，调用方可以释放 `NcmClient` 后再等待上传完成（大文件上传耗时较长）
+    pub fn upload_to_cloud(&self, file_path: &Path) -> Result<(Arc<UploadProgress>, impl Future<Output = Result<()>> + Send + 'static)> {
+        if !self.is_login() {
+            return Err(NcmError::NotLoggedIn);
+        }
 
-                break;
-            } else {
-                break;
+        let file_name = match file_path.file_name() {
+            Some(file_name) => file_name.to_string_lossy().to_string(),
+            None => return Err(NcmError::Io(std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{} is not a file", file_path.display())))),
+        };
+        let mime_type = audio_mime_type(file_path);
+        // 只读取文件大小，文件内容在上传时流式读取
+        let total_bytes = fs::metadata(file_path)?.len();
+        let file_path = file_path.to_path_buf();
+        let progress = Arc::new(UploadProgress::new(file_name.clone(), total_bytes));
+
+        let http_client = self.http_client.clone();
+        let url = format!("{}/cloud?timestamp={}", &self.api_url, Utc::now().timestamp());
+        let cookie = self.cookie.clone();
+        let upload_progress = progress.clone();
+
+        let upload = async move {
+            let result = async {
+                // 分块流式发送文件内容：请求下一块时上一块已发送，此时才计入进度
+                let file = tokio::fs::File::open(&file_path).await?;
+                let chunk_progress = upload_progress.clone();
+                let end_progress = upload_progress.clone();
+                let chunks = ReaderStream::with_capacity(file, CLOUD_UPLOAD_CHUNK_SIZE)
+                    .map(move |chunk| {
+                        if let Ok(chunk) = chunk.as_ref() {
+                            chunk_progress.chunk_requested(chunk.len() as u64);
+                        }
+                        chunk
+                    })
+                    .chain(futures_util::stream::poll_fn(move |_| {
+                        // 最后一块已发送
+                        end_progress.chunk_requested(0);
+                        Poll::Ready(None)
+                    }));
+                let part = Part::stream_with_length(Body::wrap_stream(chunks), total_bytes).file_name(file_name.clone()).mime_str(mime_type)?;
+                let form = Form::new().text("cookie", cookie.clone()).part("songFile", part);
+
+                let upload_response = http_client.post(url).multipart(form).send().await?;
+
+                let v_upload: Value = serde_json::from_slice(&upload_response.bytes().await?)?;
+
+                // 状态码报错
+                check_response_code(&v_upload, &format!("upload {} to cloud", file_name), &cookie)
+            }
+            .await;
+
+            upload_progress.finish(&result);
+            result
+        };
+
+        Ok((progress, upload))
+    }
+}
+
+/// 上报听歌记录所需的请求参数和本地队列，从 `NcmClient` 中复制出来，上报时不持有 `NcmClient` 的锁
+struct ScrobbleQueue {
+    http_client: Client,
+    api_url: String,
+    cookie: String,
+    path: PathBuf,
+    lock: Arc<tokio::sync::Mutex<()>>, // 读写队列文件时持有，防止同时进行的上报和重试互相覆盖
+}
+
+impl ScrobbleQueue {
+    async fn send(&self, song_id: u64, sourceid: u64, played_seconds: u64) -> Result<()> {
+        let scrobble_response = self
+            .http_client
+            .post(format!("{}/scrobble?id={}&sourceid={}&time={}&timestamp={}", &self.api_url, song_id, sourceid, played_seconds, Utc::now().timestamp()))
+            .form(&[("cookie", &self.cookie)])
+            .timeout(SCROBBLE_REQUEST_TIMEOUT)
+            .send()
+            .await?;
+
+        let v_scrobble: Value = serde_json::from_slice(&scrobble_response.bytes().await?)?;
+
+        // 状态码报错
+        check_response_code(&v_scrobble, &format!("scrobble song {}", song_id), &self.cookie)?;
+
+        debug!("scrobbled song {} ({}s)", song_id, played_seconds);
+
+        Ok(())
+    }
+
+    /// 追加到队列末尾
+    async fn push_back(&self, records: Vec<ScrobbleRecord>) {
+        let _guard = self.lock.lock().await;
+        let mut queue = self.read();
+        queue.extend(records);
+        self.store(&queue);
+    }
+
+    /// 放回队列开头（重试失败的记录比新记录更早）
+    async fn push_front(&self, mut records: Vec<ScrobbleRecord>) {
+        if records.is_empty() {
+            return;
+        }
+
+        let _guard = self.lock.lock().await;
+        records.extend(self.read());
+        self.store(&records);
+    }
+
+    /// 取出队列中该用户的记录，其他账号的记录保留
+    async fn take_user_records(&self, user_id: u64) -> Vec<ScrobbleRecord> {
+        let _guard = self.lock.lock().await;
+        let (user_records, other_records): (Vec<ScrobbleRecord>, Vec<ScrobbleRecord>) = self.read().into_iter().partition(|record| record.user_id == user_id);
+        self.store(&other_records);
+
+        user_records
+    }
+
+    /// 读取本地听歌记录队列（读不到则返回空队列）
+    fn read(&self) -> Vec<ScrobbleRecord> {
+        match fs::read_to_string(&self.path) {
+            Ok(queue_json) => serde_json::from_str(&queue_json).unwrap_or_else(|err| {
+                error!("failed to deserialize scrobble queue: {:?}", err);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// 保存本地听歌记录队列，队列为空时删除文件，超出 `SCROBBLE_QUEUE_MAX_LEN` 时只保存最新的记录
+    fn store(&self, records: &[ScrobbleRecord]) {
+        let records = &records[records.len().saturating_sub(SCROBBLE_QUEUE_MAX_LEN)..];
+        if records.is_empty() {
+            if let Err(err) = fs::remove_file(&self.path) {
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    error!("failed to remove scrobble queue at {:?}: {}", &self.path, err);
+                }
             }
+            return;
+        }
+
+        match serde_json::to_string(records) {
+            Ok(queue_json) => match fs::write(&self.path, queue_json) {
+                Ok(_) => debug!("scrobble queue stored at {:?}", &self.path),
+                Err(err) => error!("failed to store scrobble queue at {:?}: {}", &self.path, err),
+            },
+            Err(err) => error!("failed to serialize scrobble queue: {:?}", err),
+        }
+    }
+}
+
+/// 检查响应的状态码，`cookie` 用于区分未登录和登录已失效
+fn check_response_code(v_response: &Value, action: &str, cookie: &str) -> Result<()> {
+    match v_response["code"].as_i64() {
+        Some(200) => Ok(()),
+        // 301: 需要登录（未登录，或 cookie 已失效）
+        Some(301) if cookie.is_empty() => Err(NcmError::NotLoggedIn),
+        Some(301) => Err(NcmError::CookieExpired),
+        Some(code) => {
+            let upstream_msg = v_response["message"].as_str().or(v_response["msg"].as_str()).unwrap_or("");
+            let msg = if upstream_msg.is_empty() { format!("failed to {}", action) } else { format!("failed to {}: {}", action, upstream_msg) };
+            Err(NcmError::ApiCode(code, msg))
+        },
+        None => Err(NcmError::Decode(String::from("/code"))),
+    }
+}
+
+/// 按扩展名推断音频文件的 mime 类型
+fn audio_mime_type(file_path: &Path) -> &'static str {
+    match file_path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.to_lowercase()).as_deref() {
+        Some("mp3") => "audio/mpeg",
+        Some("flac") => "audio/flac",
+        Some("m4a") => "audio/mp4",
+        Some("ogg") => "audio/ogg",
+        Some("wav") => "audio/wav",
+        _ => "application/octet-stream",
+    }
+}
+
+/// 将 json 数组局部反序列化为 model 列表（非数组时返回空列表）
+fn parse_json_array<T: FromJson<SelfType = T>>(value: Value) -> Result<Vec<T>> {
+    let mut items = Vec::new();
+    if let Value::Array(values) = value {
+        for value in values {
+            items.push(T::from_json(value)?);
         }
     }
 
-    lyrics.reverse();
-    lyrics
+    Ok(items)
 }
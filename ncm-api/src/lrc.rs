@@ -0,0 +1,528 @@
+use crate::model::{LyricLine, LyricWord, Lyrics};
+
+/// 翻译/罗马音歌词行对齐到原文歌词行时允许的时间戳误差（ms）
+const TRANSLATION_MATCH_TOLERANCE_MS: u64 = 500;
+
+/// 逐字歌词行与歌词行匹配时允许的时间戳误差（ms）
+const YRC_MATCH_TOLERANCE_MS: u64 = 1000;
+
+/// 解析出的一行 LRC 歌词
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) struct LrcLine {
+    /// 时间戳（ms，已应用 `[offset:]`）
+    pub timestamp: u64,
+
+    /// 歌词文本（已去除增强 LRC 的逐字时间标记）
+    pub text: String,
+
+    /// 增强 LRC 的逐字时间，没有 `<mm:ss.xx>` 标记时为 None
+    pub words: Option<Vec<LyricWord>>,
+}
+
+/// 编码歌词：解析原文歌词，再按时间戳将翻译、罗马音和逐字歌词对齐到原文歌词行
+pub(crate) fn encode_lyrics(lyric_text: &str, trans_lyric_text: &str, roman_lyric_text: &str, yrc_lyric_text: &str) -> Lyrics {
+    let lrc_lines = parse_lrc(lyric_text);
+    let timestamps: Vec<u64> = lrc_lines.iter().map(|line| line.timestamp).collect();
+    let trans_lyric_lines = align_to_timestamps(&timestamps, parse_lrc(trans_lyric_text));
+    let roman_lyric_lines = align_to_timestamps(&timestamps, parse_lrc(roman_lyric_text));
+    let yrc_lines = parse_yrc(yrc_lyric_text);
+
+    lrc_lines
+        .into_iter()
+        .zip(trans_lyric_lines)
+        .zip(roman_lyric_lines)
+        .map(|((line, trans_lyric_line), roman_lyric_line)| {
+            // 增强 LRC 自带的逐字时间优先，否则使用时间戳相近且文本相同的逐字歌词行
+            let words = line.words.or_else(|| match_yrc_words(&yrc_lines, line.timestamp, &line.text));
+
+            LyricLine {
+                timestamp: line.timestamp,
+                lyric_line: line.text,
+                trans_lyric_line,
+                roman_lyric_line,
+                words,
+            }
+        })
+        .collect()
+}
+
+/// 解析 LRC / 增强 LRC 歌词，返回按时间戳排序的歌词行
+///
+/// - 一行可以有多个时间戳（`[00:12.00][01:30.00]副歌`），每个时间戳各生成一行
+/// - 分钟数不限位数，秒后的小数部分按十进制小数计算（`[mm:ss]`、`[mm:ss.x]`、`[mm:ss.xx]`、`[mm:ss.xxx]`），也兼容以 `:` 分隔的 `[mm:ss:xx]`
+/// - `[offset:+/-ms]` 整体调整时间戳（正值表示歌词提前），`[ar:]`、`[ti:]` 等其余元信息行被跳过
+/// - 没有时间戳的行（如网易云的 json 元信息行）被跳过
+pub(crate) fn parse_lrc(text: &str) -> Vec<LrcLine> {
+    let mut offset: i64 = 0;
+    let mut lines = Vec::new();
+
+    for raw_line in text.lines() {
+        let mut rest = raw_line.trim();
+        let mut timestamps = Vec::new();
+
+        // 行首连续的 `[...]` 标签
+        while let Some(tag_len) = rest.strip_prefix('[').and_then(|tag| tag.find(']')) {
+            let tag = &rest[1..=tag_len];
+            if let Some(timestamp) = parse_timestamp(tag) {
+                timestamps.push(timestamp);
+            } else if let Some((key, value)) = tag.split_once(':').filter(|(key, _)| timestamps.is_empty() && !key.is_empty() && key.chars().all(|ch| ch.is_ascii_alphabetic())) {
+                // 元信息标签，只保留 offset
+                if key.eq_ignore_ascii_case("offset") {
+                    offset = value.trim().parse().unwrap_or(offset);
+                }
+            } else {
+                // 不是标签（歌词本身以 `[` 开头）
+                break;
+            }
+            rest = &rest[tag_len + 2..];
+        }
+
+        let Some(&first_timestamp) = timestamps.first() else {
+            continue;
+        };
+
+        let (text, words) = parse_enhanced_text(rest.trim(), first_timestamp);
+        for timestamp in timestamps {
+            // 同一行的多个时间戳共用逐字时间，按时间戳平移
+            let shift = timestamp as i64 - first_timestamp as i64;
+            lines.push(LrcLine {
+                timestamp,
+                text: text.clone(),
+                words: words.as_ref().map(|words| shift_words(words, shift)),
+            });
+        }
+    }
+
+    if offset != 0 {
+        for line in lines.iter_mut() {
+            line.timestamp = line.timestamp.saturating_add_signed(-offset);
+            line.words = line.words.as_ref().map(|words| shift_words(words, -offset));
+        }
+    }
+    lines.sort_by_key(|line| line.timestamp);
+
+    lines
+}
+
+/// 解析逐字歌词（yrc），返回 (行开始时间 ms, 逐字片段)
+///
+/// 每行形如 `[行开始时间,行持续时间](字开始时间,字持续时间,0)字...`，json 格式的元信息行（作词、作曲等）被跳过
+pub(crate) fn parse_yrc(text: &str) -> Vec<(u64, Vec<LyricWord>)> {
+    let mut yrc_lines = Vec::new();
+
+    for raw_line in text.lines() {
+        let Some((line_tag, mut rest)) = raw_line.trim_end().strip_prefix('[').and_then(|line| line.split_once(']')) else {
+            continue;
+        };
+        let Some(line_start) = line_tag.split(',').next().and_then(|start| start.trim().parse::<u64>().ok()) else {
+            continue;
+        };
+
+        // 每个字的文本位于它的时间标记和下一个时间标记之间（文本中可能含有括号）
+        let mut words: Vec<LyricWord> = Vec::new();
+        let mut current: Option<LyricWord> = None;
+        while !rest.is_empty() {
+            let marker = rest.strip_prefix('(').and_then(|marker| marker.find(')').and_then(|marker_len| parse_yrc_word_marker(&marker[..marker_len]).map(|(start, duration)| (marker_len, start, duration))));
+            match marker {
+                Some((marker_len, start, duration)) => {
+                    words.extend(current.take().filter(|(_, _, text)| !text.is_empty()));
+                    current = Some((start, duration, String::new()));
+                    rest = &rest[marker_len + 2..];
+                },
+                None => {
+                    let text_len = rest.char_indices().skip(1).find(|(_, ch)| *ch == '(').map_or(rest.len(), |(i, _)| i);
+                    // 第一个时间标记之前的文本没有时间，丢弃
+                    if let Some((_, _, text)) = current.as_mut() {
+                        text.push_str(&rest[..text_len]);
+                    }
+                    rest = &rest[text_len..];
+                },
+            }
+        }
+        words.extend(current.filter(|(_, _, text)| !text.is_empty()));
+
+        if !words.is_empty() {
+            yrc_lines.push((line_start, words));
+        }
+    }
+
+    yrc_lines
+}
+
+/// 解析时间标签的内容（不含括号），如 `01:23.45`，返回 ms
+fn parse_timestamp(tag: &str) -> Option<u64> {
+    let (minutes, rest) = tag.trim().split_once(':')?;
+    let (seconds, fraction) = match rest.find(['.', ':']) {
+        Some(i) => (&rest[..i], &rest[i + 1..]),
+        None => (rest, ""),
+    };
+
+    let is_digits = |s: &str| s.chars().all(|ch| ch.is_ascii_digit());
+    if minutes.is_empty() || seconds.is_empty() || !is_digits(minutes) || !is_digits(seconds) || !is_digits(fraction) {
+        return None;
+    }
+
+    let minutes = minutes.parse::<u32>().ok()? as u64;
+    let seconds = seconds.parse::<u32>().ok()? as u64;
+    // `.5` 为 500ms，`.05` 为 50ms，超过 3 位的部分舍去
+    let millis = fraction.chars().chain(std::iter::repeat('0')).take(3).collect::<String>().parse::<u64>().ok()?;
+
+    Some((minutes * 60 + seconds) * 1000 + millis)
+}
+
+/// 解析增强 LRC 的逐字时间标记（`<mm:ss.xx>字`），返回去除标记后的文本和逐字时间（没有标记时为 None）
+///
+/// 每个字持续到下一个标记，第一个标记之前的文本从行时间戳开始，最后一个字之后没有结束标记时持续时间为 0
+fn parse_enhanced_text(text: &str, line_timestamp: u64) -> (String, Option<Vec<LyricWord>>) {
+    // (开始时间, 文本)，第一段为第一个标记之前的文本
+    let mut segments: Vec<(u64, String)> = vec![(line_timestamp, String::new())];
+    let mut rest = text;
+
+    while let Some(tag_start) = rest.find('<') {
+        let marker = rest[tag_start + 1..].find('>').and_then(|tag_len| parse_timestamp(&rest[tag_start + 1..tag_start + 1 + tag_len]).map(|timestamp| (tag_len, timestamp)));
+        let last_segment = segments.len() - 1;
+        match marker {
+            Some((tag_len, timestamp)) => {
+                segments[last_segment].1.push_str(&rest[..tag_start]);
+                segments.push((timestamp, String::new()));
+                rest = &rest[tag_start + tag_len + 2..];
+            },
+            None => {
+                // 不是时间标记，按普通文本处理
+                segments[last_segment].1.push_str(&rest[..=tag_start]);
+                rest = &rest[tag_start + 1..];
+            },
+        }
+    }
+    let last_segment = segments.len() - 1;
+    segments[last_segment].1.push_str(rest);
+
+    if segments.len() == 1 {
+        return (text.to_string(), None);
+    }
+
+    let plain_text = segments.iter().map(|(_, text)| text.as_str()).collect::<String>().trim().to_string();
+    let words = segments
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, text))| !text.trim().is_empty())
+        .map(|(i, (start, text))| {
+            let duration = segments.get(i + 1).map_or(0, |(next_start, _)| next_start.saturating_sub(*start));
+            (*start, duration, text.clone())
+        })
+        .collect();
+
+    (plain_text, Some(words))
+}
+
+/// 解析 yrc 中逐字的时间标记内容 `开始时间,持续时间,0`
+fn parse_yrc_word_marker(marker: &str) -> Option<(u64, u64)> {
+    let mut fields = marker.split(',');
+    let start = fields.next()?.trim().parse::<u64>().ok()?;
+    let duration = fields.next()?.trim().parse::<u64>().ok()?;
+    fields.next()?.trim().parse::<u64>().ok()?;
+    if fields.next().is_some() {
+        return None;
+    }
+
+    Some((start, duration))
+}
+
+fn shift_words(words: &[LyricWord], shift: i64) -> Vec<LyricWord> {
+    words.iter().map(|(start, duration, text)| (start.saturating_add_signed(shift), *duration, text.clone())).collect()
+}
+
+/// 将翻译/罗马音歌词行对齐到原文歌词行
+///
+/// 每行对齐到时间戳最近（误差不超过 `TRANSLATION_MATCH_TOLERANCE_MS`）的原文歌词行，多行对齐到同一原文歌词行时保留最近的一行；空行不参与对齐
+fn align_to_timestamps(timestamps: &[u64], lines: Vec<LrcLine>) -> Vec<Option<String>> {
+    // (误差, 文本)
+    let mut aligned: Vec<Option<(u64, String)>> = vec![None; timestamps.len()];
+
+    for line in lines.into_iter().filter(|line| !line.text.is_empty()) {
+        let nearest = timestamps
+            .iter()
+            .enumerate()
+            .map(|(index, timestamp)| (index, timestamp.abs_diff(line.timestamp)))
+            .filter(|(_, diff)| *diff <= TRANSLATION_MATCH_TOLERANCE_MS)
+            .min_by_key(|(_, diff)| *diff);
+
+        if let Some((index, diff)) = nearest {
+            if aligned[index].as_ref().is_none_or(|(aligned_diff, _)| diff < *aligned_diff) {
+                aligned[index] = Some((diff, line.text));
+            }
+        }
+    }
+
+    aligned.into_iter().map(|aligned| aligned.map(|(_, text)| text)).collect()
+}
+
+/// 找到时间戳相近（误差不超过 `YRC_MATCH_TOLERANCE_MS`）且文本相同（忽略空白）的逐字歌词行
+fn match_yrc_words(yrc_lines: &[(u64, Vec<LyricWord>)], timestamp: u64, text: &str) -> Option<Vec<LyricWord>> {
+    let text: String = text.split_whitespace().collect();
+
+    yrc_lines
+        .iter()
+        .filter(|(line_start, words)| line_start.abs_diff(timestamp) <= YRC_MATCH_TOLERANCE_MS && words.iter().flat_map(|(_, _, text)| text.split_whitespace()).collect::<String>() == text)
+        .min_by_key(|(line_start, _)| line_start.abs_diff(timestamp))
+        .map(|(_, words)| words.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timestamps_and_texts(lines: &[LrcLine]) -> Vec<(u64, &str)> {
+        lines.iter().map(|line| (line.timestamp, line.text.as_str())).collect()
+    }
+
+    fn word(start: u64, duration: u64, text: &str) -> LyricWord {
+        (start, duration, text.to_string())
+    }
+
+    #[test]
+    fn parses_netease_lyrics_with_json_metadata() {
+        let lrc = "{\"t\":0,\"c\":[{\"tx\":\"作词: \"},{\"tx\":\"方文山\"}]}\n\
+                   {\"t\":1000,\"c\":[{\"tx\":\"作曲: \"},{\"tx\":\"周杰伦\"}]}\n\
+                   [00:00.000] 作词 : 方文山\n\
+                   [00:01.000] 作曲 : 周杰伦\n\
+                   [00:24.910]素胚勾勒出青花笔锋浓转淡\n\
+                   [00:30.520]瓶身描绘的牡丹一如你初妆\n";
+
+        assert_eq!(
+            timestamps_and_texts(&parse_lrc(lrc)),
+            vec![(0, "作词 : 方文山"), (1000, "作曲 : 周杰伦"), (24910, "素胚勾勒出青花笔锋浓转淡"), (30520, "瓶身描绘的牡丹一如你初妆")]
+        );
+    }
+
+    #[test]
+    fn parses_timestamp_formats() {
+        assert_eq!(parse_timestamp("01:02"), Some(62000));
+        assert_eq!(parse_timestamp("01:02.5"), Some(62500));
+        assert_eq!(parse_timestamp("01:02.50"), Some(62500));
+        assert_eq!(parse_timestamp("01:02.05"), Some(62050));
+        assert_eq!(parse_timestamp("01:02.500"), Some(62500));
+        assert_eq!(parse_timestamp("01:02.5009"), Some(62500));
+        assert_eq!(parse_timestamp("01:02:50"), Some(62500));
+        assert_eq!(parse_timestamp("1:2.3"), Some(62300));
+        assert_eq!(parse_timestamp(" 00:10.00 "), Some(10000));
+    }
+
+    #[test]
+    fn rejects_invalid_timestamps() {
+        assert_eq!(parse_timestamp("ar:周杰伦"), None);
+        assert_eq!(parse_timestamp("offset:+200"), None);
+        assert_eq!(parse_timestamp("-1:00.00"), None);
+        assert_eq!(parse_timestamp(":10.00"), None);
+        assert_eq!(parse_timestamp("00:"), None);
+        assert_eq!(parse_timestamp("00:1a.00"), None);
+        assert_eq!(parse_timestamp("00:10.0x"), None);
+        assert_eq!(parse_timestamp("Chorus"), None);
+        assert_eq!(parse_timestamp("99999999999:00.00"), None);
+    }
+
+    #[test]
+    fn parses_three_digit_minutes() {
+        let lrc = "[99:59.99]倒数第二句\n[100:00.00]第一百分钟\n[123:45.678]很长的歌";
+
+        assert_eq!(timestamps_and_texts(&parse_lrc(lrc)), vec![(5999990, "倒数第二句"), (6000000, "第一百分钟"), (7425678, "很长的歌")]);
+    }
+
+    #[test]
+    fn expands_multiple_timestamps_and_sorts() {
+        let lrc = "[00:10.00]主歌\n[00:20.00][01:30.00][02:40.00]副歌\n[01:00.00]主歌二";
+
+        assert_eq!(timestamps_and_texts(&parse_lrc(lrc)), vec![(10000, "主歌"), (20000, "副歌"), (60000, "主歌二"), (90000, "副歌"), (160000, "副歌")]);
+    }
+
+    #[test]
+    fn keeps_order_of_lines_with_same_timestamp() {
+        let lrc = "[00:10.00]first\n[00:10.00]second";
+
+        assert_eq!(timestamps_and_texts(&parse_lrc(lrc)), vec![(10000, "first"), (10000, "second")]);
+    }
+
+    #[test]
+    fn skips_metadata_tags() {
+        let lrc = "[ti:晴天]\n[ar:周杰伦]\n[al:叶惠美]\n[by:]\n[length: 04:29]\n[re:some editor]\n[ve:1.0]\n[00:01.00]故事的小黄花";
+
+        assert_eq!(timestamps_and_texts(&parse_lrc(lrc)), vec![(1000, "故事的小黄花")]);
+    }
+
+    #[test]
+    fn applies_positive_offset() {
+        let lrc = "[offset:+200]\n[00:01.00]a\n[00:00.10]b";
+
+        assert_eq!(timestamps_and_texts(&parse_lrc(lrc)), vec![(0, "b"), (800, "a")]);
+    }
+
+    #[test]
+    fn applies_negative_offset_declared_after_lines() {
+        let lrc = "[00:01.00]a\n[offset:-500]";
+
+        assert_eq!(timestamps_and_texts(&parse_lrc(lrc)), vec![(1500, "a")]);
+    }
+
+    #[test]
+    fn ignores_invalid_offset() {
+        let lrc = "[offset:abc]\n[00:01.00]a";
+
+        assert_eq!(timestamps_and_texts(&parse_lrc(lrc)), vec![(1000, "a")]);
+    }
+
+    #[test]
+    fn keeps_brackets_in_lyric_text() {
+        let lrc = "[00:01.00][Chorus] la la la\n[00:02.00][ar:not metadata] text";
+
+        assert_eq!(timestamps_and_texts(&parse_lrc(lrc)), vec![(1000, "[Chorus] la la la"), (2000, "[ar:not metadata] text")]);
+    }
+
+    #[test]
+    fn skips_lines_without_timestamp() {
+        let lrc = "just some text\n[-1:00.00]negative\n[xx]unknown tag\n\n   \n[00:03.00]kept";
+
+        assert_eq!(timestamps_and_texts(&parse_lrc(lrc)), vec![(3000, "kept")]);
+    }
+
+    #[test]
+    fn keeps_empty_lines_as_interlude() {
+        let lrc = "[00:01.00]a\n[00:30.00]\n[00:40.00]b";
+
+        assert_eq!(timestamps_and_texts(&parse_lrc(lrc)), vec![(1000, "a"), (30000, ""), (40000, "b")]);
+    }
+
+    #[test]
+    fn trims_crlf_tabs_and_spaces() {
+        let lrc = "[00:01.00]  hello world\t\r\n[00:02.00]second\t\t\r\n";
+
+        assert_eq!(timestamps_and_texts(&parse_lrc(lrc)), vec![(1000, "hello world"), (2000, "second")]);
+    }
+
+    #[test]
+    fn parses_enhanced_lrc_words() {
+        let lrc = "[00:12.00]<00:12.00>Hello <00:12.50>beautiful <00:13.20>world<00:14.00>";
+        let lines = parse_lrc(lrc);
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].text, "Hello beautiful world");
+        assert_eq!(lines[0].words, Some(vec![word(12000, 500, "Hello "), word(12500, 700, "beautiful "), word(13200, 800, "world")]));
+    }
+
+    #[test]
+    fn enhanced_lrc_last_word_without_end_marker() {
+        let lines = parse_lrc("[00:01.00]前奏<00:01.50>第<00:02.00>二");
+
+        assert_eq!(lines[0].text, "前奏第二");
+        assert_eq!(lines[0].words, Some(vec![word(1000, 500, "前奏"), word(1500, 500, "第"), word(2000, 0, "二")]));
+    }
+
+    #[test]
+    fn enhanced_lrc_words_follow_repeated_timestamps_and_offset() {
+        let lines = parse_lrc("[offset:100]\n[00:10.00][00:20.00]<00:10.00>la <00:10.50>la<00:11.00>");
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].timestamp, 9900);
+        assert_eq!(lines[0].words, Some(vec![word(9900, 500, "la "), word(10400, 500, "la")]));
+        assert_eq!(lines[1].timestamp, 19900);
+        assert_eq!(lines[1].words, Some(vec![word(19900, 500, "la "), word(20400, 500, "la")]));
+    }
+
+    #[test]
+    fn keeps_angle_brackets_that_are_not_markers() {
+        let lines = parse_lrc("[00:01.00]I <3 you > me");
+
+        assert_eq!(lines[0].text, "I <3 you > me");
+        assert_eq!(lines[0].words, None);
+    }
+
+    #[test]
+    fn aligns_translation_by_nearest_timestamp() {
+        let lrc = "[00:01.00]One\n[00:01.30]Two\n[00:05.00]Three\n[00:09.00]Four";
+        // 时间戳有少量偏差，缺少第三行的翻译，最后一行的翻译偏差过大
+        let tlyric = "[by:translator]\n[00:01.02]一\n[00:01.29]二\n[00:10.00]四";
+        let lyrics = encode_lyrics(lrc, tlyric, "", "");
+
+        let translations: Vec<Option<&str>> = lyrics.iter().map(|line| line.trans_lyric_line.as_deref()).collect();
+        assert_eq!(translations, vec![Some("一"), Some("二"), None, None]);
+    }
+
+    #[test]
+    fn keeps_nearest_when_several_translations_match_one_line() {
+        let lyrics = encode_lyrics("[00:10.00]line", "[00:09.70]far\n[00:10.05]near\n[00:10.40]farther", "", "");
+
+        assert_eq!(lyrics[0].trans_lyric_line.as_deref(), Some("near"));
+    }
+
+    #[test]
+    fn skips_empty_translation_lines() {
+        let lyrics = encode_lyrics("[00:00.00]作词 : 某人\n[00:10.00]line", "[00:00.00]\n[00:10.00]行", "", "");
+
+        assert_eq!(lyrics[0].trans_lyric_line, None);
+        assert_eq!(lyrics[1].trans_lyric_line.as_deref(), Some("行"));
+    }
+
+    #[test]
+    fn aligns_translation_to_repeated_chorus() {
+        let lyrics = encode_lyrics("[00:20.00][01:30.00]chorus\n[00:25.00]verse", "[00:20.00][01:30.00]副歌\n[00:25.00]主歌", "", "");
+
+        let translations: Vec<(u64, Option<&str>)> = lyrics.iter().map(|line| (line.timestamp, line.trans_lyric_line.as_deref())).collect();
+        assert_eq!(translations, vec![(20000, Some("副歌")), (25000, Some("主歌")), (90000, Some("副歌"))]);
+    }
+
+    #[test]
+    fn aligns_romanization() {
+        let lyrics = encode_lyrics("[00:01.00]こんにちは\n[00:03.00]さよなら", "[00:01.00]你好", "[00:01.00]kon ni chi wa\n[00:03.01]sa yo na ra", "");
+
+        assert_eq!(lyrics[0].roman_lyric_line.as_deref(), Some("kon ni chi wa"));
+        assert_eq!(lyrics[1].roman_lyric_line.as_deref(), Some("sa yo na ra"));
+        assert_eq!(lyrics[1].trans_lyric_line, None);
+    }
+
+    #[test]
+    fn parses_yrc_lines() {
+        let yrc = "{\"t\":0,\"c\":[{\"tx\":\"作词: \"},{\"tx\":\"某人\"}]}\n\
+                   [1010,3000](1010,500,0)Hello (1510,400,0)world\r\n\
+                   [5400,2000](5400,300,0)第(5700,300,0)二(6000,300,0)句(live)\n\
+                   [9000,100]no words\n\
+                   not a yrc line";
+
+        assert_eq!(
+            parse_yrc(yrc),
+            vec![
+                (1010, vec![word(1010, 500, "Hello "), word(1510, 400, "world")]),
+                (5400, vec![word(5400, 300, "第"), word(5700, 300, "二"), word(6000, 300, "句(live)")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn matches_yrc_words_to_lyric_lines() {
+        let lrc = "[00:01.00]Hello world\n[00:05.40]第二句(live)\n[00:09.00]不同的文本";
+        let yrc = "[1010,3000](1010,500,0)Hello (1510,400,0)world\n[5400,2000](5400,300,0)第(5700,300,0)二(6000,300,0)句(live)\n[9000,1000](9000,1000,0)别的";
+        let lyrics = encode_lyrics(lrc, "", "", yrc);
+
+        assert_eq!(lyrics[0].words, Some(vec![word(1010, 500, "Hello "), word(1510, 400, "world")]));
+        assert_eq!(lyrics[1].words, Some(vec![word(5400, 300, "第"), word(5700, 300, "二"), word(6000, 300, "句(live)")]));
+        assert_eq!(lyrics[2].words, None);
+    }
+
+    #[test]
+    fn does_not_match_yrc_words_too_far_away() {
+        let lyrics = encode_lyrics("[00:01.00]Hello", "", "", "[5000,1000](5000,1000,0)Hello");
+
+        assert_eq!(lyrics[0].words, None);
+    }
+
+    #[test]
+    fn prefers_enhanced_lrc_words_over_yrc() {
+        let lyrics = encode_lyrics("[00:01.00]<00:01.00>Hi<00:02.00>", "", "", "[1000,1000](1000,300,0)Hi");
+
+        assert_eq!(lyrics[0].words, Some(vec![word(1000, 1000, "Hi")]));
+    }
+
+    #[test]
+    fn encodes_empty_lyrics() {
+        assert_eq!(encode_lyrics("", "", "", ""), Lyrics::new());
+        assert_eq!(encode_lyrics("[ti:纯音乐]\n{\"t\":0,\"c\":[{\"tx\":\"纯音乐，请欣赏\"}]}", "", "", ""), Lyrics::new());
+    }
+}
@@ -1,15 +1,46 @@
+use crate::error::NcmError;
+
 pub mod account;
+pub mod album;
+pub mod artist;
+pub mod cloud;
+pub mod comment;
 pub mod lyric;
+pub mod quality;
+pub mod scrobble;
+pub mod search;
 pub mod song;
 pub mod songlist;
 
 pub use account::*;
+pub use album::*;
+pub use artist::*;
+pub use cloud::*;
+pub use comment::*;
 pub use lyric::*;
+pub use quality::*;
+pub use scrobble::*;
+pub use search::*;
 pub use song::*;
 pub use songlist::*;
 
 pub trait FromJson {
     type SelfType;
 
-    fn from_json(value: serde_json::Value) -> anyhow::Result<Self::SelfType>;
+    fn from_json(value: serde_json::Value) -> crate::error::Result<Self::SelfType>;
+}
+
+/// 按 json pointer 路径（如 `/al/id`）读取 u64 字段，缺失或类型不符时返回 `NcmError::Decode`
+pub(crate) fn json_u64(value: &serde_json::Value, pointer: &str) -> crate::error::Result<u64> {
+    value.pointer(pointer).and_then(|v| v.as_u64()).ok_or_else(|| NcmError::Decode(pointer.to_string()))
+}
+
+/// 按 json pointer 路径读取 i64 字段，缺失或类型不符时返回 `NcmError::Decode`
+pub(crate) fn json_i64(value: &serde_json::Value, pointer: &str) -> crate::error::Result<i64> {
+    value.pointer(pointer).and_then(|v| v.as_i64()).ok_or_else(|| NcmError::Decode(pointer.to_string()))
+}
+
+/// 按 json pointer 路径读取字符串字段，缺失或类型不符时返回 `NcmError::Decode`
+pub(crate) fn json_str(value: &serde_json::Value, pointer: &str) -> crate::error::Result<String> {
+    value.pointer(pointer).and_then(|v| v.as_str()).map(|s| s.to_string()).ok_or_else(|| NcmError::Decode(pointer.to_string()))
 }
@@ -1,5 +1,5 @@
-use crate::model::FromJson;
-use anyhow::Result;
+use crate::error::Result;
+use crate::model::{json_i64, json_str, json_u64, FromJson};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -18,9 +18,9 @@ impl FromJson for Account {
     type SelfType = Account;
 
     fn from_json(value: Value) -> Result<Self::SelfType> {
-        let user_id = value["userId"].as_u64().unwrap();
-        let nickname = value["nickname"].as_str().unwrap().to_string();
-        let vip_type = value["vipType"].as_i64().unwrap();
+        let user_id = json_u64(&value, "/userId")?;
+        let nickname = json_str(&value, "/nickname")?;
+        let vip_type = json_i64(&value, "/vipType")?;
 
         Ok(Account { user_id, nickname, vip_type })
     }
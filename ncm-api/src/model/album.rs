@@ -0,0 +1,63 @@
+use crate::error::Result;
+use crate::model::song::Song;
+use crate::model::{json_str, json_u64, FromJson};
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[allow(unused)]
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
+pub struct Album {
+    /// 专辑名
+    pub name: String,
+
+    /// 专辑 id
+    pub id: u64,
+
+    /// 歌手
+    pub artist: String,
+
+    /// 歌手 id
+    pub artist_id: u64,
+
+    /// 歌曲数量
+    pub songs_count: usize,
+
+    /// 发行时间（ms 时间戳）
+    pub publish_time: i64,
+
+    /// 专辑介绍
+    pub description: String,
+
+    /// 专辑内的歌曲
+    pub songs: Vec<Song>,
+}
+
+impl FromJson for Album {
+    type SelfType = Album;
+
+    fn from_json(value: Value) -> Result<Self::SelfType> {
+        Ok(Album {
+            name: json_str(&value, "/name")?,
+            id: json_u64(&value, "/id")?,
+            artist: value["artist"]["name"].as_str().unwrap_or("Unknown").to_string(),
+            artist_id: value["artist"]["id"].as_u64().unwrap_or(0),
+            songs_count: value["size"].as_u64().unwrap_or(0) as usize,
+            publish_time: value["publishTime"].as_i64().unwrap_or(0),
+            description: value["description"].as_str().unwrap_or("").to_string(),
+            songs: Vec::new(),
+        })
+    }
+}
+
+impl Album {
+    /// 发行日期（`YYYY-MM-DD`）
+    pub fn publish_date(&self) -> String {
+        // 发行时间以北京时间（UTC+8）零点记录
+        let beijing_offset = FixedOffset::east_opt(8 * 3600).unwrap();
+        match DateTime::from_timestamp_millis(self.publish_time) {
+            Some(date_time) if self.publish_time > 0 => date_time.with_timezone(&beijing_offset).format("%Y-%m-%d").to_string(),
+            _ => String::from("未知"),
+        }
+    }
+}
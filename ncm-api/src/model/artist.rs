@@ -0,0 +1,47 @@
+use crate::error::Result;
+use crate::model::{json_str, json_u64, FromJson};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[allow(unused)]
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
+pub struct Artist {
+    /// 歌手名
+    pub name: String,
+
+    /// 歌手 id
+    pub id: u64,
+
+    /// 专辑数量
+    pub albums_count: usize,
+
+    /// 歌曲数量
+    pub songs_count: usize,
+
+    /// 歌手简介
+    pub description: String,
+}
+
+impl FromJson for Artist {
+    type SelfType = Artist;
+
+    fn from_json(value: Value) -> Result<Self::SelfType> {
+        Ok(Artist {
+            name: json_str(&value, "/name")?,
+            id: json_u64(&value, "/id")?,
+            albums_count: value["albumSize"].as_u64().unwrap_or(0) as usize,
+            songs_count: value["musicSize"].as_u64().unwrap_or(0) as usize,
+            description: value["briefDesc"].as_str().unwrap_or("").to_string(),
+        })
+    }
+}
+
+/// 歌曲中引用的歌手，只有歌手名和 id（完整信息需另行获取 `Artist`）
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
+pub struct ArtistRef {
+    /// 歌手名
+    pub name: String,
+
+    /// 歌手 id（云盘中未匹配曲库的歌曲为 0）
+    pub id: u64,
+}
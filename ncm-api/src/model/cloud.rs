@@ -0,0 +1,66 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// 云盘上传进度，在上传任务和界面之间共享
+#[derive(Debug)]
+pub struct UploadProgress {
+    /// 上传的文件名
+    pub file_name: String,
+
+    /// 文件大小（字节）
+    pub total_bytes: u64,
+
+    sent_bytes: AtomicU64,
+    // 已交给 http 请求、尚未确认发送的字节数
+    pending_bytes: AtomicU64,
+    // 上传结束后的结果，失败时为错误信息
+    result: Mutex<Option<std::result::Result<(), String>>>,
+}
+
+impl UploadProgress {
+    pub fn new(file_name: String, total_bytes: u64) -> Self {
+        Self {
+            file_name,
+            total_bytes,
+            sent_bytes: AtomicU64::new(0),
+            pending_bytes: AtomicU64::new(0),
+            result: Mutex::new(None),
+        }
+    }
+
+    /// 已发送的字节数
+    pub fn sent_bytes(&self) -> u64 {
+        self.sent_bytes.load(Ordering::Relaxed)
+    }
+
+    /// 已发送的比例（0.0 ~ 1.0）
+    pub fn ratio(&self) -> f64 {
+        if self.total_bytes == 0 {
+            return 1.0;
+        }
+
+        (self.sent_bytes() as f64 / self.total_bytes as f64).min(1.0)
+    }
+
+    /// 文件已全部发送，等待服务端处理（匹配曲库、转存）
+    pub fn is_processing(&self) -> bool {
+        self.result().is_none() && self.sent_bytes() >= self.total_bytes
+    }
+
+    /// 上传结果，上传中为 None
+    pub fn result(&self) -> Option<std::result::Result<(), String>> {
+        self.result.lock().map(|result| result.clone()).unwrap_or(None)
+    }
+
+    /// http 请求读取了下一块（`bytes` 字节，读取结束时为 0），说明上一块已发送
+    pub(crate) fn chunk_requested(&self, bytes: u64) {
+        let sent = self.pending_bytes.swap(bytes, Ordering::Relaxed);
+        self.sent_bytes.fetch_add(sent, Ordering::Relaxed);
+    }
+
+    pub(crate) fn finish(&self, result: &crate::error::Result<()>) {
+        if let Ok(mut guard) = self.result.lock() {
+            *guard = Some(result.as_ref().map(|_| ()).map_err(|err| err.to_string()));
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use crate::error::Result;
+use crate::model::{json_i64, json_str, json_u64, FromJson};
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+#[allow(unused)]
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
+pub struct Comment {
+    /// 评论 id
+    pub id: u64,
+
+    /// 评论者 id
+    pub user_id: u64,
+
+    /// 评论者昵称
+    pub nickname: String,
+
+    /// 评论内容
+    pub content: String,
+
+    /// 点赞数
+    pub liked_count: u64,
+
+    /// 评论时间（ms 时间戳）
+    pub time: i64,
+
+    /// 被回复的评论 (评论者昵称, 评论内容)，非回复时为 None
+    pub replied: Option<(String, String)>,
+}
+
+impl FromJson for Comment {
+    type SelfType = Comment;
+
+    fn from_json(value: Value) -> Result<Self::SelfType> {
+        Ok(Comment {
+            id: json_u64(&value, "/commentId")?,
+            user_id: value["user"]["userId"].as_u64().unwrap_or(0),
+            nickname: value["user"]["nickname"].as_str().unwrap_or("").to_string(),
+            content: json_str(&value, "/content")?,
+            liked_count: value["likedCount"].as_u64().unwrap_or(0),
+            time: json_i64(&value, "/time")?,
+            replied: value["beReplied"][0]["content"].as_str().map(|content| {
+                let nickname = value["beReplied"][0]["user"]["nickname"].as_str().unwrap_or("").to_string();
+                (nickname, content.to_string())
+            }),
+        })
+    }
+}
+
+impl Comment {
+    /// 评论时间（`YYYY-MM-DD HH:MM`，北京时间）
+    pub fn date_time(&self) -> String {
+        let beijing_offset = FixedOffset::east_opt(8 * 3600).unwrap();
+        match DateTime::from_timestamp_millis(self.time) {
+            Some(date_time) => date_time.with_timezone(&beijing_offset).format("%Y-%m-%d %H:%M").to_string(),
+            None => String::from("未知"),
+        }
+    }
+}
+
+/// 评论排序方式
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CommentSortType {
+    /// 推荐
+    Recommend,
+    /// 最热
+    Hot,
+    /// 最新
+    Time,
+}
+
+impl CommentSortType {
+    /// `/comment/new` 接口的 sortType 参数
+    pub fn code(&self) -> usize {
+        match self {
+            CommentSortType::Recommend => 1,
+            CommentSortType::Hot => 2,
+            CommentSortType::Time => 3,
+        }
+    }
+}
+
+impl fmt::Display for CommentSortType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommentSortType::Recommend => write!(f, "推荐"),
+            CommentSortType::Hot => write!(f, "最热"),
+            CommentSortType::Time => write!(f, "最新"),
+        }
+    }
+}
+
+/// 一页评论
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CommentPage {
+    /// 本页的评论
+    pub comments: Vec<Comment>,
+
+    /// 评论总数
+    pub total_count: u64,
+
+    /// 获取下一页所需的游标，没有下一页时为 None
+    pub next_cursor: Option<String>,
+}
@@ -2,6 +2,9 @@ use serde::{Deserialize, Serialize};
 
 pub type Lyrics = Vec<LyricLine>;
 
+/// 逐字歌词片段 (开始时间 ms, 持续时间 ms, 文本)
+pub type LyricWord = (u64, u64, String);
+
 #[allow(unused)]
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
 pub struct LyricLine {
@@ -20,4 +23,8 @@ pub struct LyricLine {
     /// 罗马音歌词行
     #[serde(rename = "r")]
     pub roman_lyric_line: Option<String>,
+
+    /// 逐字歌词，无逐字歌词时为 None
+    #[serde(rename = "w", default)]
+    pub words: Option<Vec<LyricWord>>,
 }
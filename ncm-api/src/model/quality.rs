@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+/// 音质等级，对应 `/song/url/v1` 接口的 `level` 参数
+///
+/// 只表示等级本身，显示用的名称由 UI 决定
+#[derive(Debug, PartialEq, Eq, Hash, Deserialize, Serialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum QualityLevel {
+    /// 标准
+    Standard,
+    /// 较高
+    Higher,
+    /// 极高
+    Exhigh,
+    /// 无损
+    Lossless,
+    /// Hi-Res
+    Hires,
+    /// 高清环绕声
+    Jyeffect,
+    /// 沉浸环绕声
+    Sky,
+    /// 杜比全景声
+    Dolby,
+    /// 超清母带
+    Jymaster,
+}
+
+impl QualityLevel {
+    /// 所有音质等级，按从低到高排列
+    pub const ALL: [QualityLevel; 9] = [
+        QualityLevel::Standard,
+        QualityLevel::Higher,
+        QualityLevel::Exhigh,
+        QualityLevel::Lossless,
+        QualityLevel::Hires,
+        QualityLevel::Jyeffect,
+        QualityLevel::Sky,
+        QualityLevel::Dolby,
+        QualityLevel::Jymaster,
+    ];
+
+    /// 接口中使用的等级名
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QualityLevel::Standard => "standard",
+            QualityLevel::Higher => "higher",
+            QualityLevel::Exhigh => "exhigh",
+            QualityLevel::Lossless => "lossless",
+            QualityLevel::Hires => "hires",
+            QualityLevel::Jyeffect => "jyeffect",
+            QualityLevel::Sky => "sky",
+            QualityLevel::Dolby => "dolby",
+            QualityLevel::Jymaster => "jymaster",
+        }
+    }
+
+    /// 由接口中的等级名解析，未知等级返回 None
+    pub fn from_level(level: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|quality_level| quality_level.as_str() == level)
+    }
+
+    /// 当前等级不可用时回退到的下一级，`Standard` 没有更低的等级
+    ///
+    /// 环绕声和母带均回退到 Hi-Res，之后按 无损 -> 极高 -> 较高 -> 标准 依次回退
+    pub fn fallback(&self) -> Option<Self> {
+        match self {
+            QualityLevel::Standard => None,
+            QualityLevel::Higher => Some(QualityLevel::Standard),
+            QualityLevel::Exhigh => Some(QualityLevel::Higher),
+            QualityLevel::Lossless => Some(QualityLevel::Exhigh),
+            QualityLevel::Hires => Some(QualityLevel::Lossless),
+            QualityLevel::Jyeffect | QualityLevel::Sky | QualityLevel::Dolby | QualityLevel::Jymaster => Some(QualityLevel::Hires),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// 一条听歌记录，上报失败时保存到本地队列，下次启动后重试
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
+pub struct ScrobbleRecord {
+    /// 播放时登录的用户 id ，只在同一账号登录时重试
+    pub user_id: u64,
+    /// 歌曲 id
+    pub song_id: u64,
+    /// 来源歌单 id（不来自歌单时为 0）
+    pub sourceid: u64,
+    /// 播放时长（秒）
+    pub played_seconds: u64,
+}
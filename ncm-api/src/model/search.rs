@@ -0,0 +1,97 @@
+use crate::model::{Album, Artist, Song, Songlist};
+use std::fmt;
+
+/// 搜索类型
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SearchType {
+    Song,
+    Album,
+    Artist,
+    Songlist,
+    Lyric,
+}
+
+impl SearchType {
+    /// `/cloudsearch` 接口的 type 参数
+    pub fn code(&self) -> usize {
+        match self {
+            SearchType::Song => 1,
+            SearchType::Album => 10,
+            SearchType::Artist => 100,
+            SearchType::Songlist => 1000,
+            SearchType::Lyric => 1006,
+        }
+    }
+
+    /// 按顺序切换到下一个搜索类型
+    pub fn next(&self) -> Self {
+        match self {
+            SearchType::Song => SearchType::Album,
+            SearchType::Album => SearchType::Artist,
+            SearchType::Artist => SearchType::Songlist,
+            SearchType::Songlist => SearchType::Lyric,
+            SearchType::Lyric => SearchType::Song,
+        }
+    }
+}
+
+impl fmt::Display for SearchType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchType::Song => write!(f, "单曲"),
+            SearchType::Album => write!(f, "专辑"),
+            SearchType::Artist => write!(f, "歌手"),
+            SearchType::Songlist => write!(f, "歌单"),
+            SearchType::Lyric => write!(f, "歌词"),
+        }
+    }
+}
+
+/// 搜索结果
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SearchResult {
+    Songs(Vec<Song>),
+    Albums(Vec<Album>),
+    Artists(Vec<Artist>),
+    Songlists(Vec<Songlist>),
+    /// 歌曲及其匹配到的歌词片段
+    Lyrics(Vec<(Song, String)>),
+}
+
+impl SearchResult {
+    pub fn search_type(&self) -> SearchType {
+        match self {
+            SearchResult::Songs(_) => SearchType::Song,
+            SearchResult::Albums(_) => SearchType::Album,
+            SearchResult::Artists(_) => SearchType::Artist,
+            SearchResult::Songlists(_) => SearchType::Songlist,
+            SearchResult::Lyrics(_) => SearchType::Lyric,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            SearchResult::Songs(songs) => songs.len(),
+            SearchResult::Albums(albums) => albums.len(),
+            SearchResult::Artists(artists) => artists.len(),
+            SearchResult::Songlists(songlists) => songlists.len(),
+            SearchResult::Lyrics(lyrics) => lyrics.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 追加下一页结果（类型不同时直接替换）
+    pub fn append(&mut self, other: SearchResult) {
+        match (self, other) {
+            (SearchResult::Songs(songs), SearchResult::Songs(other)) => songs.extend(other),
+            (SearchResult::Albums(albums), SearchResult::Albums(other)) => albums.extend(other),
+            (SearchResult::Artists(artists), SearchResult::Artists(other)) => artists.extend(other),
+            (SearchResult::Songlists(songlists), SearchResult::Songlists(other)) => songlists.extend(other),
+            (SearchResult::Lyrics(lyrics), SearchResult::Lyrics(other)) => lyrics.extend(other),
+            (this, other) => *this = other,
+        }
+    }
+}
@@ -1,4 +1,7 @@
+use crate::error::Result;
+use crate::model::{json_str, json_u64, ArtistRef, FromJson, QualityLevel};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 #[allow(unused)]
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
@@ -7,18 +10,102 @@ pub struct Song {
     pub name: String,
     /// 歌曲 id
     pub id: u64,
-    /// 歌手
-    pub singer: String,
-    /// 歌手 id
-    pub singer_id: u64,
+    /// 歌手（合作歌曲有多位）
+    pub artists: Vec<ArtistRef>,
     /// 专辑
     pub album: String,
     /// 专辑 id
     pub album_id: u64,
     /// 歌曲时长
     pub duration: u64,
+    /// 专辑封面链接
+    #[serde(default)]
+    pub cover_url: Option<String>,
     /// 歌曲链接
     pub song_url: Option<String>,
-    /// 音质
-    pub quality_level: String,
+    /// 实际获取到的音质（装载歌曲 url 后才有）
+    pub quality_level: Option<QualityLevel>,
+    /// 推荐理由（仅每日推荐歌曲）
+    #[serde(default)]
+    pub recommend_reason: Option<String>,
+    /// 是否为心动模式推荐的歌曲（不在用户歌单中）
+    #[serde(default)]
+    pub is_recommended: bool,
+    /// 是否为云盘中的歌曲（用户上传，播放不受版权限制）
+    #[serde(default)]
+    pub is_cloud: bool,
+}
+
+impl FromJson for Song {
+    type SelfType = Song;
+
+    fn from_json(value: Value) -> Result<Self::SelfType> {
+        // 部分接口（如私人FM）仍返回旧格式的歌曲字段
+        let (ar, al, dt) = if value["ar"].is_null() { ("artists", "album", "duration") } else { ("ar", "al", "dt") };
+
+        Ok(Song {
+            name: json_str(&value, "/name")?,
+            id: json_u64(&value, "/id")?,
+            artists: value[ar]
+                .as_array()
+                .map(|artists| {
+                    artists
+                        .iter()
+                        .map(|artist| ArtistRef {
+                            name: artist["name"].as_str().unwrap_or("Unknown").to_string(),
+                            id: artist["id"].as_u64().unwrap_or(0),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            album: value[al]["name"].as_str().unwrap_or("Unknown").to_string(),
+            album_id: json_u64(&value, &format!("/{}/id", al))?,
+            duration: json_u64(&value, &format!("/{}", dt))?,
+            cover_url: value[al]["picUrl"].as_str().map(|url| url.to_string()),
+            song_url: None,
+            quality_level: None,
+            recommend_reason: None,
+            is_recommended: false,
+            is_cloud: false,
+        })
+    }
+}
+
+impl Song {
+    /// 解析 `/user/cloud` 返回的云盘歌曲条目
+    ///
+    /// 优先使用匹配到的曲库信息 `simpleSong`，未匹配（或字段不全）时使用上传文件中的歌名、歌手和专辑
+    pub(crate) fn from_cloud_json(mut value: Value) -> Result<Song> {
+        let song = match Song::from_json(value["simpleSong"].take()) {
+            Ok(song) => song,
+            Err(_) => Song {
+                name: json_str(&value, "/songName")?,
+                id: json_u64(&value, "/songId")?,
+                artists: vec![ArtistRef {
+                    name: value["artist"].as_str().filter(|name| !name.is_empty()).unwrap_or("Unknown").to_string(),
+                    id: 0,
+                }],
+                album: value["album"].as_str().filter(|name| !name.is_empty()).unwrap_or("Unknown").to_string(),
+                album_id: 0,
+                duration: 0,
+                cover_url: None,
+                song_url: None,
+                quality_level: None,
+                recommend_reason: None,
+                is_recommended: false,
+                is_cloud: false,
+            },
+        };
+
+        Ok(Song { is_cloud: true, ..song })
+    }
+
+    /// 所有歌手名，以 " / " 分隔
+    pub fn artists_name(&self) -> String {
+        if self.artists.is_empty() {
+            return String::from("Unknown");
+        }
+
+        self.artists.iter().map(|artist| artist.name.as_str()).collect::<Vec<&str>>().join(" / ")
+    }
 }
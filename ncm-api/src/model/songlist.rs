@@ -1,5 +1,8 @@
+use crate::error::Result;
 use crate::model::song::Song;
+use crate::model::{json_str, json_u64, FromJson};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 #[allow(unused)]
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
@@ -16,6 +19,82 @@ pub struct Songlist {
     /// 创建者
     pub creator: String,
 
+    /// 创建者 id
+    pub creator_id: u64,
+
+    /// 是否为收藏（而非自己创建）的歌单
+    pub subscribed: bool,
+
+    /// 歌单封面链接
+    #[serde(default)]
+    pub cover_url: Option<String>,
+
     /// 歌单内的歌曲
     pub songs: Vec<Song>,
 }
+
+impl Songlist {
+    /// 每日推荐伪歌单的 id（真实歌单 id 不为 0）
+    pub const DAILY_RECOMMEND_ID: u64 = 0;
+
+    /// 每日推荐伪歌单，歌曲在装载时通过 `/recommend/songs` 获取
+    pub fn daily_recommend() -> Self {
+        Songlist {
+            name: String::from("每日推荐"),
+            id: Self::DAILY_RECOMMEND_ID,
+            songs_count: 0,
+            creator: String::from("网易云音乐"),
+            creator_id: 0,
+            subscribed: false,
+            cover_url: None,
+            songs: Vec::new(),
+        }
+    }
+
+    /// 云盘伪歌单的 id
+    pub const CLOUD_DRIVE_ID: u64 = u64::MAX;
+
+    /// 云盘伪歌单，歌曲在装载时通过 `/user/cloud` 获取
+    pub fn cloud_drive() -> Self {
+        Songlist {
+            name: String::from("我的云盘"),
+            id: Self::CLOUD_DRIVE_ID,
+            songs_count: 0,
+            creator: String::from("网易云音乐"),
+            creator_id: 0,
+            subscribed: false,
+            cover_url: None,
+            songs: Vec::new(),
+        }
+    }
+
+    pub fn is_daily_recommend(&self) -> bool {
+        self.id == Self::DAILY_RECOMMEND_ID
+    }
+
+    pub fn is_cloud_drive(&self) -> bool {
+        self.id == Self::CLOUD_DRIVE_ID
+    }
+
+    /// 是否为伪歌单（每日推荐、云盘），伪歌单不能编辑、收藏
+    pub fn is_pseudo(&self) -> bool {
+        self.is_daily_recommend() || self.is_cloud_drive()
+    }
+}
+
+impl FromJson for Songlist {
+    type SelfType = Songlist;
+
+    fn from_json(value: Value) -> Result<Self::SelfType> {
+        Ok(Songlist {
+            name: json_str(&value, "/name")?,
+            id: json_u64(&value, "/id")?,
+            songs_count: value["trackCount"].as_u64().unwrap_or(0) as usize,
+            creator: value["creator"]["nickname"].as_str().unwrap_or("").to_string(),
+            creator_id: value["creator"]["userId"].as_u64().or(value["userId"].as_u64()).unwrap_or(0),
+            subscribed: value["subscribed"].as_bool().unwrap_or(false),
+            cover_url: value["coverImgUrl"].as_str().map(|url| url.to_string()),
+            songs: Vec::new(),
+        })
+    }
+}
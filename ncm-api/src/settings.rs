@@ -1,3 +1,4 @@
+use crate::model::QualityLevel;
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -5,6 +6,10 @@ use serde::{Deserialize, Serialize};
 pub struct Settings {
     pub use_remote_api: bool,
     pub remote_api_url: String,
+    /// 是否将播放记录上报到网易云（计入云端记录和听歌报告）
+    pub enable_scrobble: bool,
+    /// 优先获取的音质，不可用时依次回退到更低的音质
+    pub preferred_quality: QualityLevel,
 }
 
 impl Default for Settings {
@@ -12,6 +17,8 @@ impl Default for Settings {
         Self {
             use_remote_api: false,
             remote_api_url: String::from("https://ncm-api-wine.vercel.app/"),
+            enable_scrobble: true,
+            preferred_quality: QualityLevel::Jymaster,
         }
     }
 }
@@ -15,12 +15,29 @@ pub enum PlayState {
     Ended,
 }
 
+/// 一首歌播放结束或被切换时生成的播放记录，用于上报听歌记录
+#[derive(Clone, Debug)]
+pub struct PlayedTrack {
+    /// 歌曲 id
+    pub song_id: u64,
+    /// 来源歌单 id（不来自歌单时为 0）
+    pub sourceid: u64,
+    /// 播放时长（秒）
+    pub played_seconds: u64,
+    /// 是否完整播放（否则为被切换）
+    pub finished: bool,
+}
+
 #[derive(Clone, Debug)]
 pub enum PlayMode {
     Single,
     SingleRepeat,
     ListRepeat,
     Shuffle,
+    /// 私人FM，播放列表为不断追加的推荐队列
+    PersonalFm,
+    /// 心动模式，在“我喜欢的音乐”中穿插推荐歌曲
+    Intelligence,
 }
 
 impl fmt::Display for PlayMode {
@@ -30,6 +47,8 @@ impl fmt::Display for PlayMode {
             PlayMode::SingleRepeat => write!(f, "单曲循环"),
             PlayMode::ListRepeat => write!(f, "列表循环"),
             PlayMode::Shuffle => write!(f, "随机播放"),
+            PlayMode::PersonalFm => write!(f, "私人FM"),
+            PlayMode::Intelligence => write!(f, "心动模式"),
         }
     }
 }
@@ -3,27 +3,49 @@ pub use config::*;
 
 use anyhow::{anyhow, Result};
 use gstreamer::ClockTime;
-use gstreamer_play::{gst, Play, PlayVideoRenderer};
-use log::{debug, trace};
+use gstreamer::glib;
+use gstreamer_play::{gst, Play, PlayMessage, PlayVideoRenderer};
+use log::{debug, error, trace, warn};
 use ncm_api::model::Songlist;
 use ncm_api::{
     model::{Lyrics, Song},
-    NcmClient,
+    MusicBackend,
 };
 use rand::{thread_rng, Rng};
-use tokio::sync::MutexGuard;
+use std::time::{Duration, Instant};
+
+/// 私人FM播放列表名
+const PERSONAL_FM_PLAYLIST_NAME: &str = "私人FM";
+/// 私人FM队列中未播放的歌曲不多于该数量时，预取下一批
+const PERSONAL_FM_PREFETCH_THRESHOLD: usize = 1;
+/// 私人FM队列耗尽且预取失败后，等待该时长再自动重试预取
+const PERSONAL_FM_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+/// 被切换的歌曲播放超过该时长（秒）才生成播放记录
+const TRACK_PLAYED_MIN_SECONDS: u64 = 30;
+/// 相似歌曲电台播放列表名前缀
+const SONG_RADIO_PLAYLIST_NAME: &str = "相似歌曲电台";
+/// 心动模式中每隔多少首歌单内歌曲插入一首推荐歌曲
+const INTELLIGENCE_INTERLEAVE_STEP: usize = 2;
+/// 同一首歌连续刷新链接的最大次数，超过后放弃，防止新链接同样不可用时反复重试
+const SONG_URL_REFRESH_MAX_ATTEMPTS: usize = 3;
 
 pub struct Player {
     play: Play,
+    play_bus: gst::Bus, // gstreamer_play 的消息总线，用于接收播放出错等消息
     //
     play_state: PlayState,
     play_mode: PlayMode,
+    play_mode_before_radio: Option<PlayMode>, // 进入私人FM/心动模式/相似歌曲电台前的播放模式，离开时恢复
+    song_radio: bool,                         // 当前播放列表为相似歌曲电台，列表循环到末尾时继续追加相似歌曲
+    personal_fm_retry_at: Option<Instant>,    // 私人FM队列耗尽且预取失败时，下次自动重试预取的时间
     //
     volume: f64,
     //
     songlists: Vec<Songlist>,
+    songlists_version: usize, // 歌单列表每次变化时递增，界面据此判断是否需要刷新
     //
     current_playlist_name: String,
+    current_playlist_id: u64, // 当前播放列表对应的歌单 id（不来自歌单时为 0），作为播放记录的来源
     current_playlist: Vec<Song>, // TODO: 优化为指针
     //
     play_index_history_stack: Vec<usize>, // 历史记录，保存播放的歌曲在 playlist 中的 index，栈顶为当前播放
@@ -33,6 +55,12 @@ pub struct Player {
     //
     current_song_lyrics: Option<Lyrics>,
     current_lyric_line_index: Option<usize>,
+    //
+    playing_track: Option<(u64, u64)>, // 正在播放的 (歌曲 id, 来源歌单 id)，播放结束或被切换时生成播放记录
+    played_tracks: Vec<PlayedTrack>,   // 尚未被取走的播放记录
+    //
+    last_known_position: Option<ClockTime>, // 最近一次已知的播放位置，链接失效重新加载后从这里继续播放
+    song_url_refresh_attempts: usize,       // 当前歌曲已刷新链接的次数
 }
 
 impl Player {
@@ -50,19 +78,31 @@ impl Player {
         let volume = 0.2;
         play.set_volume(volume);
 
+        let play_bus = play.message_bus();
+
         Self {
             play,
+            play_bus,
             play_state: PlayState::Stopped,
             play_mode: PlayMode::Shuffle,
+            play_mode_before_radio: None,
+            song_radio: false,
+            personal_fm_retry_at: None,
             volume,
             songlists: Vec::new(),
+            songlists_version: 0,
             current_playlist_name: String::new(),
+            current_playlist_id: 0,
             current_playlist: Vec::new(),
             play_index_history_stack: Vec::new(),
             current_song_index: None,
             current_song: None,
             current_song_lyrics: None,
             current_lyric_line_index: None,
+            playing_track: None,
+            played_tracks: Vec::new(),
+            last_known_position: None,
+            song_url_refresh_attempts: 0,
         }
     }
 }
@@ -98,6 +138,16 @@ impl Player {
 
     pub fn set_play_mode(&mut self, mode: PlayMode) {
         self.play_mode = mode;
+        self.play_mode_before_radio = None;
+    }
+
+    pub fn is_personal_fm(&self) -> bool {
+        matches!(self.play_mode, PlayMode::PersonalFm)
+    }
+
+    /// 是否处于私人FM/心动模式（播放列表由推荐生成，切换歌单时退出）
+    pub fn is_radio_mode(&self) -> bool {
+        matches!(self.play_mode, PlayMode::PersonalFm | PlayMode::Intelligence)
     }
 
     pub fn duration(&self) -> Option<ClockTime> {
@@ -112,6 +162,11 @@ impl Player {
         &self.current_playlist_name
     }
 
+    /// 当前播放列表对应的歌单 id（不来自歌单时为 0）
+    pub fn current_playlist_id(&self) -> u64 {
+        self.current_playlist_id
+    }
+
     pub fn current_playlist(&self) -> &Vec<Song> {
         &self.current_playlist
     }
@@ -134,26 +189,112 @@ impl Player {
 
     pub fn set_songlists(&mut self, songlists: Vec<Songlist>) {
         self.songlists = songlists;
+        self.songlists_version += 1;
+    }
+
+    /// 追加分页加载的歌单
+    pub fn append_songlists(&mut self, songlists: Vec<Songlist>) {
+        self.songlists.extend(songlists);
+        self.songlists_version += 1;
     }
 
     pub fn songlists(&self) -> &Vec<Songlist> {
         &self.songlists
     }
+
+    pub fn songlists_version(&self) -> usize {
+        self.songlists_version
+    }
+}
+
+/// 歌单编辑（远端修改成功后同步到内存，不重新获取）
+impl Player {
+    /// 新建的歌单插入到“我喜欢的音乐”之后，收藏的歌单插入到收藏歌单的最前
+    pub fn add_songlist(&mut self, songlist: Songlist) {
+        let index = if songlist.subscribed {
+            self.songlists.iter().position(|songlist| songlist.subscribed).unwrap_or(self.songlists.len())
+        } else {
+            self.songlists.iter().position(|songlist| !songlist.is_pseudo()).map_or(self.songlists.len(), |index| index + 1)
+        };
+        self.songlists.insert(index, songlist);
+        self.songlists_version += 1;
+    }
+
+    pub fn remove_songlist(&mut self, songlist_id: u64) {
+        self.songlists.retain(|songlist| songlist.id != songlist_id);
+        self.songlists_version += 1;
+    }
+
+    pub fn rename_songlist(&mut self, songlist_id: u64, name: &str) {
+        if let Some(songlist) = self.songlists.iter_mut().find(|songlist| songlist.id == songlist_id) {
+            songlist.name = name.to_string();
+            self.songlists_version += 1;
+        }
+
+        if self.current_playlist_id == songlist_id && !self.is_radio_mode() {
+            self.current_playlist_name = name.to_string();
+        }
+    }
+
+    /// 向歌单添加歌曲（与网易云一致，添加到歌单最前），已在歌单中的歌曲不重复添加
+    pub fn add_songs_to_songlist(&mut self, songlist_id: u64, songs: Vec<Song>) {
+        if let Some(songlist) = self.songlists.iter_mut().find(|songlist| songlist.id == songlist_id) {
+            let new_songs: Vec<Song> = songs.into_iter().filter(|song| !songlist.songs.iter().any(|s| s.id == song.id)).collect();
+
+            // 歌单歌曲未装载时无法判断是否重复，不更新，切换到该歌单时会重新装载
+            if !songlist.songs.is_empty() {
+                songlist.songs_count += new_songs.len();
+                songlist.songs.splice(0..0, new_songs.clone());
+            }
+            self.songlists_version += 1;
+
+            if self.current_playlist_id == songlist_id && !self.is_radio_mode() {
+                self.current_playlist.splice(0..0, new_songs);
+                self.resync_current_song_index();
+            }
+        }
+    }
+
+    /// 从歌单删除歌曲
+    pub fn remove_songs_from_songlist(&mut self, songlist_id: u64, song_ids: &[u64]) {
+        if let Some(songlist) = self.songlists.iter_mut().find(|songlist| songlist.id == songlist_id) {
+            songlist.songs_count = songlist.songs_count.saturating_sub(song_ids.len());
+            songlist.songs.retain(|song| !song_ids.contains(&song.id));
+            self.songlists_version += 1;
+        }
+
+        if self.current_playlist_id == songlist_id && !self.is_radio_mode() {
+            self.current_playlist.retain(|song| !song_ids.contains(&song.id));
+            self.resync_current_song_index();
+        }
+    }
+
+    /// 播放列表变化后，根据当前歌曲重新定位 current_song_index ，并清空播放历史（历史中的 index 已失效）
+    fn resync_current_song_index(&mut self) {
+        self.current_song_index = match self.current_song.as_ref() {
+            Some(current_song) => self.current_playlist.iter().position(|song| song.id == current_song.id),
+            None => None,
+        };
+        self.play_index_history_stack = self.current_song_index.into_iter().collect();
+    }
 }
 
 /// playlist
 impl Player {
     /// 切换播放列表
-    pub async fn switch_playlist<'c>(&mut self, playlist_candidate_index: usize, ncm_client_guard: MutexGuard<'c, NcmClient>) -> Result<()> {
+    pub async fn switch_playlist<B: MusicBackend>(&mut self, playlist_candidate_index: usize, backend: &B) -> Result<()> {
         if let Some(songlist) = self.songlists.get_mut(playlist_candidate_index) {
             debug!("{:?}", songlist);
 
             //
-            ncm_client_guard.load_songlist_songs(songlist).await?;
+            backend.load_songlist_songs(songlist).await?;
 
             //
             self.current_playlist_name = songlist.name.clone();
+            // 伪歌单（每日推荐、云盘）不是真实歌单，不作为播放记录的来源
+            self.current_playlist_id = if songlist.is_pseudo() { 0 } else { songlist.id };
             self.current_playlist = songlist.songs.clone();
+            self.leave_radio_mode();
             self.play_index_history_stack = Vec::new();
             self.current_song_index = if self.current_playlist.is_empty() { None } else { Some(0) };
 
@@ -163,6 +304,40 @@ impl Player {
         }
     }
 
+    /// 切换到自定义播放列表（搜索结果等不属于用户歌单的歌曲）
+    pub fn switch_custom_playlist(&mut self, playlist_name: String, playlist: Vec<Song>) {
+        self.leave_radio_mode();
+        self.current_playlist_name = playlist_name;
+        self.current_playlist_id = 0;
+        self.current_playlist = playlist;
+        self.play_index_history_stack = Vec::new();
+        self.current_song_index = if self.current_playlist.is_empty() { None } else { Some(0) };
+    }
+
+    /// 停止播放，并清除歌单、播放列表和播放历史（登出时调用）
+    pub fn reset(&mut self) {
+        self.play.stop();
+        self.play_state = PlayState::Stopped;
+        self.leave_radio_mode();
+        self.songlists = Vec::new();
+        self.songlists_version += 1;
+        self.current_playlist_name = String::new();
+        self.current_playlist_id = 0;
+        self.current_playlist = Vec::new();
+        self.play_index_history_stack = Vec::new();
+        self.current_song_index = None;
+        self.current_song = None;
+        self.current_song_lyrics = None;
+        self.current_lyric_line_index = None;
+        self.playing_track = None;
+        self.played_tracks = Vec::new();
+    }
+
+    /// 取走已生成的播放记录（歌曲播放结束，或播放一定时长后被切换）
+    pub fn take_played_tracks(&mut self) -> Vec<PlayedTrack> {
+        std::mem::take(&mut self.played_tracks)
+    }
+
     /// 向后搜索歌单（向上方搜索）
     pub fn search_backward_playlist(&mut self, start_index: usize, keywords: Vec<String>) -> Option<usize> {
         if start_index < self.current_playlist.len() {
@@ -191,6 +366,17 @@ impl Player {
 }
 
 #[inline]
+/// gstreamer 的播放错误是否为链接失效（HTTP 403/404）
+///
+/// souphttpsrc 会在错误详情中附带 `http-status-code`，没有详情时按资源错误类型判断
+fn is_expired_url_error(error: &glib::Error, details: Option<&gst::StructureRef>) -> bool {
+    if let Some(status_code) = details.and_then(|details| details.get::<u32>("http-status-code").ok()) {
+        return matches!(status_code, 403 | 404);
+    }
+
+    error.matches(gst::ResourceError::NotAuthorized) || error.matches(gst::ResourceError::NotFound)
+}
+
 fn search_in_iter<'c, I>(mut playlist_iter: I, keywords: Vec<String>) -> Option<usize>
 where
     I: Iterator<Item = (usize, &'c Song)>,
@@ -225,7 +411,10 @@ impl Player {
     }
 
     /// 自动播放
-    pub async fn auto_play<'c>(&mut self, ncm_client_guard: MutexGuard<'c, NcmClient>) -> Result<()> {
+    pub async fn auto_play<B: MusicBackend>(&mut self, backend: &B) -> Result<()> {
+        // 处理 gstreamer 的消息（链接过期等）
+        self.handle_play_messages(backend).await?;
+
         // 判断一首歌是否播放完
         if self.play_state == PlayState::Playing {
             if let (Some(position), Some(duration)) = (self.position(), self.duration()) {
@@ -234,6 +423,7 @@ impl Player {
 
                 if duration_msec - position_msec <= 10 {
                     self.play_state = PlayState::Ended;
+                    self.record_played_track(true);
                 }
             }
         }
@@ -242,45 +432,59 @@ impl Player {
             // 当前歌曲仍在播放，推进歌词
             self.auto_lyric_forward();
         } else if self.play_state == PlayState::Ended {
+            // 私人FM等待重试预取期间保持 Ended
+            if self.is_personal_fm() && self.personal_fm_retry_at.is_some_and(|retry_at| Instant::now() < retry_at) {
+                return Ok(());
+            }
+
             // 播放下一首
+            self.prefetch_personal_fm(backend).await;
+            if self.is_personal_fm_exhausted() {
+                // 预取失败且队列已耗尽：不停止播放也不退出私人FM，稍后重试
+                self.personal_fm_retry_at = Some(Instant::now() + PERSONAL_FM_RETRY_INTERVAL);
+                return Ok(());
+            }
+            self.extend_song_radio(backend).await;
             self.update_next_to_play();
-            self.play_next(ncm_client_guard).await?;
+            self.play_next(backend).await?;
         }
 
         Ok(())
     }
 
     /// 立刻播放指定歌曲
-    pub async fn play_particularly_now<'c>(&mut self, index_to_play: usize, ncm_client_guard: MutexGuard<'c, NcmClient>) -> Result<()> {
+    pub async fn play_particularly_now<B: MusicBackend>(&mut self, index_to_play: usize, backend: &B) -> Result<()> {
         if index_to_play < self.current_playlist.len() {
             self.play_state = PlayState::Playing;
             self.current_song_index = Some(index_to_play);
             self.current_song = Some(self.current_playlist[index_to_play].clone());
 
-            self.play_next(ncm_client_guard).await?;
+            self.play_next(backend).await?;
         }
 
         Ok(())
     }
 
     /// 根据当前模式开始播放
-    pub async fn start_play<'c>(&mut self, ncm_client_guard: MutexGuard<'c, NcmClient>) -> Result<()> {
+    pub async fn start_play<B: MusicBackend>(&mut self, backend: &B) -> Result<()> {
         if !self.current_playlist.is_empty() {
             match self.play_mode {
                 PlayMode::ListRepeat => {
                     self.current_song_index = Some(0);
                     self.current_song = Some(self.current_playlist[0].clone());
-                    self.play_next(ncm_client_guard).await?;
+                    self.play_next(backend).await?;
                     Ok(())
                 },
                 PlayMode::Shuffle => {
                     let index = thread_rng().gen_range(0..self.current_playlist.len());
                     self.current_song_index = Some(index);
                     self.current_song = Some(self.current_playlist[index].clone());
-                    self.play_next(ncm_client_guard).await?;
+                    self.play_next(backend).await?;
                     Ok(())
                 },
-                _ => Err(anyhow!("start命令只在`列表循环`和`随机播放`模式下有效")),
+                PlayMode::PersonalFm => self.start_personal_fm(backend).await,
+                PlayMode::Intelligence => self.start_intelligence(backend).await,
+                _ => Err(anyhow!("start命令只在`列表循环`、`随机播放`、`私人FM`和`心动模式`下有效")),
             }
         } else {
             Err(anyhow!("请先选择歌单"))
@@ -288,16 +492,22 @@ impl Player {
     }
 
     /// 立刻播放下一首
-    pub async fn play_next_song_now<'c>(&mut self, ncm_client_guard: MutexGuard<'c, NcmClient>) -> Result<()> {
+    pub async fn play_next_song_now<B: MusicBackend>(&mut self, backend: &B) -> Result<()> {
         if self.play_state == PlayState::Playing || self.play_state == PlayState::Paused || self.play_state == PlayState::Ended {
             // 当前单曲播放半秒后才可以切换到下一首，留出缓冲时间，防止切换过快
             if let Some(position) = self.position() {
                 if position.mseconds() >= 500 {
+                    // 手动切换时立刻重试预取，不等待自动重试
+                    self.prefetch_personal_fm(backend).await;
+                    if self.is_personal_fm_exhausted() {
+                        return Err(anyhow!("私人FM暂时获取不到新歌曲，请稍后再试"));
+                    }
+                    self.extend_song_radio(backend).await;
                     self.update_next_to_play();
 
                     debug!("[{:?}] {:?}, ", self.current_song_index, self.current_song);
 
-                    self.play_next(ncm_client_guard).await?;
+                    self.play_next(backend).await?;
                 }
             }
         }
@@ -306,7 +516,7 @@ impl Player {
     }
 
     /// 立刻播放上一首
-    pub async fn play_prev_song_now<'c>(&mut self, ncm_client_guard: MutexGuard<'c, NcmClient>) -> Result<()> {
+    pub async fn play_prev_song_now<B: MusicBackend>(&mut self, backend: &B) -> Result<()> {
         // 当前单曲播放半秒后才可以切换到上一首，留出缓冲时间，防止切换过快
         if let Some(position) = self.position() {
             if position.mseconds() >= 500 {
@@ -316,7 +526,7 @@ impl Player {
                         // 播放上一首
                         self.current_song_index = Some(prev_song_index);
                         self.current_song = Some(self.current_playlist[prev_song_index].clone());
-                        self.play_next(ncm_client_guard).await?;
+                        self.play_next(backend).await?;
                     } else {
                         // 无上一首（当前为第一首播放）
                         self.play_index_history_stack.push(current_song_index);
@@ -328,6 +538,143 @@ impl Player {
         Ok(())
     }
 
+    /// 进入私人FM模式，以获取的第一批歌曲作为播放列表并开始播放
+    pub async fn start_personal_fm<B: MusicBackend>(&mut self, backend: &B) -> Result<()> {
+        let songs = backend.get_personal_fm().await?;
+        if songs.is_empty() {
+            return Err(anyhow!("私人FM暂无推荐歌曲"));
+        }
+
+        self.enter_radio_mode(PlayMode::PersonalFm);
+        self.current_playlist_name = String::from(PERSONAL_FM_PLAYLIST_NAME);
+        self.current_playlist_id = 0;
+        self.current_playlist = songs;
+        self.play_index_history_stack = Vec::new();
+        self.current_song_index = Some(0);
+        self.current_song = Some(self.current_playlist[0].clone());
+
+        self.play_next(backend).await
+    }
+
+    /// 进入心动模式
+    ///
+    /// 以当前歌曲（不在“我喜欢的音乐”中时为其第一首）为种子获取推荐，将推荐歌曲穿插到“我喜欢的音乐”中作为播放列表，
+    /// 从种子歌曲开始顺序播放
+    pub async fn start_intelligence<B: MusicBackend>(&mut self, backend: &B) -> Result<()> {
+        // 用户歌单中第一个真实歌单即“我喜欢的音乐”
+        let mut liked_songlist = match self.songlists.iter().find(|songlist| !songlist.is_pseudo()) {
+            Some(songlist) => songlist.clone(),
+            None => return Err(anyhow!("未找到“我喜欢的音乐”歌单")),
+        };
+        backend.load_songlist_songs(&mut liked_songlist).await?;
+        if liked_songlist.songs.is_empty() {
+            return Err(anyhow!("“我喜欢的音乐”中没有歌曲"));
+        }
+
+        // 种子歌曲
+        let seed_index = self
+            .current_song
+            .as_ref()
+            .and_then(|current_song| liked_songlist.songs.iter().position(|song| song.id == current_song.id))
+            .unwrap_or(0);
+        let seed_song = liked_songlist.songs[seed_index].clone();
+
+        // 只取推荐歌曲，歌单内的歌曲已在播放列表中
+        let recommended_songs: Vec<Song> = backend
+            .get_intelligence_list(seed_song.id, liked_songlist.id)
+            .await?
+            .into_iter()
+            .filter(|song| song.is_recommended)
+            .collect();
+
+        // 从种子歌曲开始，每隔 INTELLIGENCE_INTERLEAVE_STEP 首插入一首推荐歌曲
+        let mut playlist = liked_songlist.songs[..=seed_index].to_vec();
+        let mut recommended_iter = recommended_songs.into_iter();
+        for (i, song) in liked_songlist.songs[seed_index + 1..].iter().enumerate() {
+            if i % INTELLIGENCE_INTERLEAVE_STEP == 0 {
+                if let Some(recommended_song) = recommended_iter.next() {
+                    playlist.push(recommended_song);
+                }
+            }
+            playlist.push(song.clone());
+        }
+        playlist.extend(recommended_iter);
+
+        self.enter_radio_mode(PlayMode::Intelligence);
+        self.current_playlist_name = format!("心动模式 - {}", liked_songlist.name);
+        self.current_playlist_id = liked_songlist.id;
+        self.current_playlist = playlist;
+        self.play_index_history_stack = Vec::new();
+        self.current_song_index = Some(seed_index);
+
+        // 种子歌曲正在播放时不打断
+        let is_seed_playing = self.current_song.as_ref().is_some_and(|song| song.id == seed_song.id) && self.play_state != PlayState::Stopped;
+        if is_seed_playing {
+            self.play_index_history_stack.push(seed_index);
+            Ok(())
+        } else {
+            self.current_song = Some(seed_song);
+            self.play_next(backend).await
+        }
+    }
+
+    /// 以当前歌曲为种子开启相似歌曲电台
+    ///
+    /// 播放列表替换为当前歌曲及其相似歌曲，以列表循环顺序播放，播放到末尾时以最后一首为种子继续追加
+    pub async fn start_song_radio<B: MusicBackend>(&mut self, backend: &B) -> Result<()> {
+        let Some(seed_song) = self.current_song.clone() else {
+            return Err(anyhow!("请先播放一首歌曲"));
+        };
+
+        let similar_songs: Vec<Song> = backend.get_similar_songs(seed_song.id).await?.into_iter().filter(|song| song.id != seed_song.id).collect();
+        if similar_songs.is_empty() {
+            return Err(anyhow!("`{}`暂无相似歌曲", seed_song.name));
+        }
+
+        self.leave_radio_mode();
+        self.play_mode_before_radio = Some(self.play_mode.clone());
+        self.play_mode = PlayMode::ListRepeat;
+        self.song_radio = true;
+        self.current_playlist_name = format!("{} - {}", SONG_RADIO_PLAYLIST_NAME, seed_song.name);
+        self.current_playlist_id = 0;
+        self.current_playlist = std::iter::once(seed_song).chain(similar_songs).collect();
+        self.play_index_history_stack = Vec::new();
+        self.current_song_index = Some(0);
+
+        // 种子歌曲正在播放时不打断
+        if self.play_state != PlayState::Stopped {
+            self.play_index_history_stack.push(0);
+            Ok(())
+        } else {
+            self.play_next(backend).await
+        }
+    }
+
+    /// 私人FM中“不喜欢”当前歌曲，并立刻播放下一首
+    pub async fn fm_trash_current_song<B: MusicBackend>(&mut self, backend: &B) -> Result<()> {
+        if !self.is_personal_fm() {
+            return Err(anyhow!("只能在私人FM模式下标记不喜欢"));
+        }
+
+        if let Some(song) = self.current_song.as_ref() {
+            backend.fm_trash(song.id).await?;
+
+            self.prefetch_personal_fm(backend).await;
+            if self.is_personal_fm_exhausted() {
+                // 不再播放不喜欢的歌曲，由 auto_play 稍后重试预取
+                self.record_played_track(false);
+                self.play.stop();
+                self.play_state = PlayState::Ended;
+                self.personal_fm_retry_at = Some(Instant::now() + PERSONAL_FM_RETRY_INTERVAL);
+                return Ok(());
+            }
+            self.update_next_to_play();
+            self.play_next(backend).await?;
+        }
+
+        Ok(())
+    }
+
     /// 跳转到所给编号的时间戳处播放
     pub async fn seek_to_timestamp_with_index(&mut self, index: usize) -> Result<()> {
         if self.play_state == PlayState::Playing || self.play_state == PlayState::Paused || self.play_state == PlayState::Ended {
@@ -336,6 +683,7 @@ impl Player {
                     self.current_lyric_line_index = Some(index);
                     let timestamp = current_song_lyrics[index].timestamp;
                     self.play.seek(ClockTime::from_mseconds(timestamp));
+                    self.last_known_position = Some(ClockTime::from_mseconds(timestamp));
                 }
             }
         }
@@ -352,7 +700,7 @@ impl Player {
         self.current_song = match self.play_mode {
             PlayMode::Single => None,
             PlayMode::SingleRepeat => self.current_song.clone(),
-            PlayMode::ListRepeat => {
+            PlayMode::ListRepeat | PlayMode::Intelligence => {
                 if let Some(mut index) = self.current_song_index {
                     index += 1;
                     if index >= self.current_playlist.len() {
@@ -373,16 +721,99 @@ impl Player {
                     None
                 }
             },
+            PlayMode::PersonalFm => {
+                // 队列由 prefetch_personal_fm() 保证足够长，到达末尾（预取失败）时返回 None ，调用方应先检查 is_personal_fm_exhausted() 并等待重试
+                match self.current_song_index.map(|index| index + 1) {
+                    Some(index) if index < self.current_playlist.len() => {
+                        self.current_song_index = Some(index);
+                        Some(self.current_playlist[index].clone())
+                    },
+                    _ => None,
+                }
+            },
         };
     }
 
+    /// 私人FM队列已播放到最后一首（预取失败），没有下一首可播放
+    fn is_personal_fm_exhausted(&self) -> bool {
+        self.is_personal_fm() && self.current_song_index.is_none_or(|index| index + 1 >= self.current_playlist.len())
+    }
+
+    /// 私人FM队列即将播放完时，预取下一批歌曲追加到播放列表末尾
+    ///
+    /// 获取失败时只记录日志（队列中仍有歌曲时照常播放，下次再预取）
+    async fn prefetch_personal_fm<B: MusicBackend>(&mut self, backend: &B) {
+        if !self.is_personal_fm() {
+            return;
+        }
+
+        let remaining = match self.current_song_index {
+            Some(index) => self.current_playlist.len().saturating_sub(index + 1),
+            None => 0,
+        };
+        if remaining <= PERSONAL_FM_PREFETCH_THRESHOLD {
+            match backend.get_personal_fm().await {
+                Ok(songs) => {
+                    debug!("prefetch {} personal fm songs", songs.len());
+                    self.current_playlist.extend(songs);
+                    self.personal_fm_retry_at = None;
+                },
+                Err(err) => error!("failed to prefetch personal fm: {:?}", err),
+            }
+        }
+    }
+
+    /// 相似歌曲电台以列表循环播放到最后一首时，以最后一首为种子追加相似歌曲（跳过已在列表中的）
+    ///
+    /// 获取失败时只记录日志，照常回到列表开头循环
+    async fn extend_song_radio<B: MusicBackend>(&mut self, backend: &B) {
+        if !self.song_radio || !matches!(self.play_mode, PlayMode::ListRepeat) {
+            return;
+        }
+
+        let Some(last_song_id) = self.current_song_index.filter(|index| index + 1 >= self.current_playlist.len()).and_then(|index| self.current_playlist.get(index)).map(|song| song.id) else {
+            return;
+        };
+
+        match backend.get_similar_songs(last_song_id).await {
+            Ok(songs) => {
+                let songs: Vec<Song> = songs.into_iter().filter(|song| !self.current_playlist.iter().any(|s| s.id == song.id)).collect();
+                debug!("extend song radio with {} songs", songs.len());
+                self.current_playlist.extend(songs);
+            },
+            Err(err) => error!("failed to extend song radio from song {}: {:?}", last_song_id, err),
+        }
+    }
+
+    /// 进入私人FM/心动模式，记录进入前的播放模式（从相似歌曲电台进入时沿用电台之前的模式）
+    fn enter_radio_mode(&mut self, mode: PlayMode) {
+        if !self.is_radio_mode() && !self.song_radio {
+            self.play_mode_before_radio = Some(self.play_mode.clone());
+        }
+        self.play_mode = mode;
+        self.song_radio = false;
+    }
+
+    /// 离开私人FM/心动模式或相似歌曲电台，恢复进入前的播放模式
+    fn leave_radio_mode(&mut self) {
+        if self.is_radio_mode() {
+            self.play_mode = self.play_mode_before_radio.take().unwrap_or(PlayMode::Shuffle);
+        } else if self.song_radio {
+            // 电台中手动切换过播放模式时（已清除记录）保持当前模式
+            if let Some(play_mode) = self.play_mode_before_radio.take() {
+                self.play_mode = play_mode;
+            }
+        }
+        self.song_radio = false;
+    }
+
     /// 播放下一首
-    async fn play_next<'c>(&mut self, ncm_client_guard: MutexGuard<'c, NcmClient>) -> Result<()> {
+    async fn play_next<B: MusicBackend>(&mut self, backend: &B) -> Result<()> {
         if let Some(mut song) = self.current_song.clone() {
-            // 检查歌曲是否可获取（版权/会员/...限制）
-            if ncm_client_guard.check_song_availability(song.id).await? {
+            // 检查歌曲是否可获取（版权/会员/...限制），云盘歌曲不受限制
+            if song.is_cloud || backend.check_song_availability(song.id).await? {
                 // 获取歌曲 uri
-                ncm_client_guard.load_song_url(&mut song).await?;
+                backend.load_song_url(&mut song).await?;
 
                 // 更新当前歌曲信息
                 self.current_song = Some(song.clone());
@@ -394,10 +825,11 @@ impl Player {
                     }
 
                     // 获取歌词
-                    self.update_current_song_lyrics(ncm_client_guard).await?;
+                    self.update_current_song_lyrics(backend).await?;
 
                     // 播放
                     self.play_new_song_by_uri(url.as_str()).await;
+                    self.playing_track = Some((song.id, self.current_playlist_id));
 
                     // 播放状态
                     self.play_state = PlayState::Playing;
@@ -417,6 +849,12 @@ impl Player {
     }
 
     async fn play_new_song_by_uri(&mut self, uri: &str) {
+        // 上一首被切换
+        self.record_played_track(false);
+
+        self.last_known_position = None;
+        self.song_url_refresh_attempts = 0;
+
         self.play.stop();
         self.play.set_uri(Some(uri));
         self.play.play();
@@ -429,9 +867,92 @@ impl Player {
         self.play.set_volume(self.volume);
     }
 
-    async fn update_current_song_lyrics<'c>(&mut self, ncm_client_guard: MutexGuard<'c, NcmClient>) -> Result<()> {
+    /// 取出 gstreamer_play 总线上的所有消息，记录播放位置，当前链接失效（HTTP 403/404）时重新获取链接
+    async fn handle_play_messages<B: MusicBackend>(&mut self, backend: &B) -> Result<()> {
+        let mut url_expired = false;
+
+        while let Some(msg) = self.play_bus.pop() {
+            match PlayMessage::parse(&msg) {
+                Ok(PlayMessage::PositionUpdated { position: Some(position) }) if position.mseconds() > 0 => {
+                    self.last_known_position = Some(position);
+                },
+                Ok(PlayMessage::Error { error, details }) => {
+                    warn!("gstreamer play error: {:?}, details: {:?}", error, details);
+
+                    // 只处理当前链接的错误，切歌前残留的消息忽略
+                    let current_url = self.current_song.as_ref().and_then(|song| song.song_url.clone());
+                    if is_expired_url_error(&error, details.as_deref()) && current_url.is_some() && self.play.uri().map(|uri| uri.to_string()) == current_url {
+                        url_expired = true;
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        if url_expired {
+            self.refresh_current_song_url(backend).await?;
+        }
+
+        Ok(())
+    }
+
+    /// 重新获取当前歌曲的链接，从最近一次已知的播放位置继续播放，不改变播放/暂停状态
+    async fn refresh_current_song_url<B: MusicBackend>(&mut self, backend: &B) -> Result<()> {
+        let Some(mut song) = self.current_song.clone() else {
+            return Ok(());
+        };
+
+        if self.song_url_refresh_attempts >= SONG_URL_REFRESH_MAX_ATTEMPTS {
+            return Err(anyhow!("歌曲`{}`的链接已失效，刷新{}次后仍无法播放", song.name, SONG_URL_REFRESH_MAX_ATTEMPTS));
+        }
+        self.song_url_refresh_attempts += 1;
+
+        backend.load_song_url(&mut song).await?;
+        let Some(url) = song.song_url.clone() else {
+            return Err(anyhow!("歌曲`{}`的链接已失效，且无法重新获取", song.name));
+        };
+        self.current_song = Some(song);
+
+        let position = self.last_known_position;
+        debug!("song url expired, reload at {:?} (attempt {})", position, self.song_url_refresh_attempts);
+
+        self.play.stop();
+        self.play.set_uri(Some(url.as_str()));
+        self.play.play();
+        if let Some(position) = position {
+            self.play.seek(position);
+        }
+        if self.play_state == PlayState::Paused {
+            self.play.pause();
+        }
+
+        // 与 play_new_song_by_uri() 相同，等待 gstreamer 端更新并恢复音量
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        self.play.set_volume(self.volume);
+
+        Ok(())
+    }
+
+    /// 为正在播放的歌曲生成播放记录，被切换的歌曲播放时长不足 TRACK_PLAYED_MIN_SECONDS 时不记录
+    fn record_played_track(&mut self, finished: bool) {
+        if let Some((song_id, sourceid)) = self.playing_track.take() {
+            let played_seconds = self.position().map(|position| position.seconds()).unwrap_or(0);
+
+            if finished || played_seconds >= TRACK_PLAYED_MIN_SECONDS {
+                debug!("track played: {} ({}s, finished: {})", song_id, played_seconds, finished);
+                self.played_tracks.push(PlayedTrack {
+                    song_id,
+                    sourceid,
+                    played_seconds,
+                    finished,
+                });
+            }
+        }
+    }
+
+    async fn update_current_song_lyrics<B: MusicBackend>(&mut self, backend: &B) -> Result<()> {
         if let Some(current_song) = self.current_song.as_ref() {
-            if let Ok(lyrics) = ncm_client_guard.get_song_lyrics(current_song.id).await {
+            if let Ok(lyrics) = backend.get_song_lyrics(current_song.id).await {
                 if !lyrics.is_empty() {
                     self.current_song_lyrics = Some(lyrics);
                     self.current_lyric_line_index = Some(0);
@@ -464,3 +985,249 @@ impl Player {
         }
     }
 }
+
+/// 这些测试会创建 `Player`（调用 `gst::init`），需要 gstreamer 运行环境，默认忽略，
+/// 使用 `cargo test -p ncm-play -- --ignored` 运行
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ncm_api::model::Account;
+    use ncm_api::MemoryBackend;
+
+    fn song(id: u64) -> Song {
+        Song {
+            name: format!("song {}", id),
+            id,
+            artists: Vec::new(),
+            album: String::new(),
+            album_id: 0,
+            duration: 0,
+            cover_url: None,
+            song_url: None,
+            quality_level: None,
+            recommend_reason: None,
+            is_recommended: false,
+            is_cloud: false,
+        }
+    }
+
+    fn songlist(id: u64, song_ids: &[u64]) -> Songlist {
+        Songlist {
+            name: format!("songlist {}", id),
+            id,
+            songs_count: song_ids.len(),
+            creator: String::new(),
+            creator_id: 0,
+            subscribed: false,
+            cover_url: None,
+            songs: song_ids.iter().map(|&id| song(id)).collect(),
+        }
+    }
+
+    /// 已登录的后端，所有歌曲都有链接
+    fn backend(songlists: Vec<Songlist>) -> MemoryBackend {
+        let account = Account {
+            user_id: 1,
+            nickname: String::from("tester"),
+            vip_type: 0,
+        };
+        let mut backend = MemoryBackend::new(Some(account), songlists);
+        for song in backend.songlists.iter().flat_map(|songlist| songlist.songs.iter()) {
+            backend.song_urls.insert(song.id, format!("http://localhost/{}.mp3", song.id));
+        }
+
+        backend
+    }
+
+    /// 歌单列表与 ncm-tui 一致，从后端获取（不含歌曲），切换时再装载
+    async fn player_with(backend: &MemoryBackend) -> Player {
+        let mut player = Player::new();
+        player.set_songlists(backend.get_user_all_songlists().await.unwrap());
+
+        player
+    }
+
+    fn current_song_id(player: &Player) -> Option<u64> {
+        player.current_song.as_ref().map(|song| song.id)
+    }
+
+    #[tokio::test]
+    #[ignore = "需要 gstreamer 运行环境"]
+    async fn switch_playlist_loads_songs_from_backend() {
+        let backend = backend(vec![songlist(11, &[1, 2]), songlist(12, &[3, 4, 5])]);
+        let mut player = player_with(&backend).await;
+        assert!(player.songlists()[1].songs.is_empty());
+
+        player.switch_playlist(1, &backend).await.unwrap();
+
+        assert_eq!(player.current_playlist_name(), "songlist 12");
+        assert_eq!(player.current_playlist_id(), 12);
+        assert_eq!(player.current_playlist().iter().map(|song| song.id).collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(player.current_song_index(), Some(0));
+        assert!(player.switch_playlist(2, &backend).await.is_err());
+    }
+
+    #[tokio::test]
+    #[ignore = "需要 gstreamer 运行环境"]
+    async fn switch_playlist_does_not_use_pseudo_songlist_as_source() {
+        let mut daily_recommend = Songlist::daily_recommend();
+        daily_recommend.songs = vec![song(1)];
+        let mut cloud_drive = Songlist::cloud_drive();
+        cloud_drive.songs = vec![song(2)];
+        let backend = backend(vec![daily_recommend, cloud_drive]);
+        let mut player = player_with(&backend).await;
+
+        player.switch_playlist(1, &backend).await.unwrap();
+
+        assert_eq!(player.current_playlist_id(), 0);
+        assert_eq!(player.current_playlist().len(), 1);
+    }
+
+    #[tokio::test]
+    #[ignore = "需要 gstreamer 运行环境"]
+    async fn update_next_to_play_follows_play_mode() {
+        let backend = backend(vec![songlist(11, &[1, 2, 3])]);
+        let mut player = player_with(&backend).await;
+        player.switch_playlist(0, &backend).await.unwrap();
+
+        // 列表循环：到末尾后回到第一首
+        player.set_play_mode(PlayMode::ListRepeat);
+        let mut played = Vec::new();
+        for _ in 0..4 {
+            player.update_next_to_play();
+            played.push(current_song_id(&player).unwrap());
+        }
+        assert_eq!(played, vec![2, 3, 1, 2]);
+
+        // 单曲循环：保持当前歌曲
+        player.set_play_mode(PlayMode::SingleRepeat);
+        player.update_next_to_play();
+        assert_eq!(current_song_id(&player), Some(2));
+        assert_eq!(player.current_song_index(), Some(1));
+
+        // 随机播放：始终在播放列表内
+        player.set_play_mode(PlayMode::Shuffle);
+        for _ in 0..10 {
+            player.update_next_to_play();
+            let index = player.current_song_index().unwrap();
+            assert_eq!(current_song_id(&player), Some(player.current_playlist()[index].id));
+        }
+
+        // 单曲播放：播放完即停止
+        player.set_play_mode(PlayMode::Single);
+        player.update_next_to_play();
+        assert_eq!(current_song_id(&player), None);
+    }
+
+    #[tokio::test]
+    #[ignore = "需要 gstreamer 运行环境"]
+    async fn start_play_loads_url_and_records_source() {
+        let backend = backend(vec![songlist(11, &[1, 2])]);
+        let mut player = player_with(&backend).await;
+        player.switch_playlist(0, &backend).await.unwrap();
+        player.set_play_mode(PlayMode::ListRepeat);
+
+        player.start_play(&backend).await.unwrap();
+
+        assert!(player.play_state == PlayState::Playing);
+        assert_eq!(player.current_song.as_ref().and_then(|song| song.song_url.clone()), Some(String::from("http://localhost/1.mp3")));
+        assert_eq!(player.playing_track, Some((1, 11)));
+        assert_eq!(player.play_index_history_stack, vec![0]);
+    }
+
+    #[tokio::test]
+    #[ignore = "需要 gstreamer 运行环境"]
+    async fn unavailable_song_is_skipped() {
+        let mut backend = backend(vec![songlist(11, &[1, 2])]);
+        backend.unavailable_song_ids.insert(1);
+        let mut player = player_with(&backend).await;
+        player.switch_playlist(0, &backend).await.unwrap();
+        player.set_play_mode(PlayMode::ListRepeat);
+
+        player.start_play(&backend).await.unwrap();
+
+        // 不可获取的歌曲不播放，由播放循环继续寻找下一首
+        assert!(player.play_state == PlayState::Ended);
+        assert_eq!(player.playing_track, None);
+        assert!(player.play_index_history_stack.is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "需要 gstreamer 运行环境"]
+    async fn song_radio_restores_play_mode_when_switching_playlist() {
+        let mut backend = backend(vec![songlist(11, &[1, 2])]);
+        backend.similar_songs = vec![song(1), song(21), song(22)];
+        let mut player = player_with(&backend).await;
+        player.switch_playlist(0, &backend).await.unwrap();
+        player.set_play_mode(PlayMode::SingleRepeat);
+        player.current_song = Some(song(1));
+
+        player.start_song_radio(&backend).await.unwrap();
+
+        // 种子歌曲在前，相似歌曲中的种子歌曲被去掉
+        assert_eq!(player.current_playlist().iter().map(|song| song.id).collect::<Vec<_>>(), vec![1, 21, 22]);
+        assert!(matches!(player.play_mode, PlayMode::ListRepeat));
+
+        player.switch_playlist(0, &backend).await.unwrap();
+
+        assert!(!player.song_radio);
+        assert!(matches!(player.play_mode, PlayMode::SingleRepeat));
+    }
+
+    #[tokio::test]
+    #[ignore = "需要 gstreamer 运行环境"]
+    async fn songlist_edits_do_not_touch_radio_playlist() {
+        let mut backend = backend(vec![songlist(11, &[1, 2]), songlist(12, &[3])]);
+        backend.personal_fm_songs = vec![song(2), song(31)];
+        let mut player = player_with(&backend).await;
+        player.switch_playlist(0, &backend).await.unwrap();
+
+        // 已在歌单中的歌曲不重复计数，未装载的歌单不更新数量
+        player.add_songs_to_songlist(11, vec![song(1), song(4)]);
+        player.add_songs_to_songlist(12, vec![song(3)]);
+        assert_eq!(player.songlists()[0].songs_count, 3);
+        assert_eq!(player.songlists()[1].songs_count, 1);
+        assert_eq!(player.current_playlist().len(), 3);
+
+        // 私人FM的播放列表不随歌单变化
+        player.start_personal_fm(&backend).await.unwrap();
+        player.current_playlist_id = 11;
+        player.remove_songs_from_songlist(11, &[2]);
+        player.add_songs_to_songlist(11, vec![song(5)]);
+        assert_eq!(player.current_playlist().iter().map(|song| song.id).collect::<Vec<_>>(), vec![2, 31]);
+        assert_eq!(player.songlists()[0].songs.iter().map(|song| song.id).collect::<Vec<_>>(), vec![5, 4, 1]);
+    }
+
+    #[tokio::test]
+    #[ignore = "需要 gstreamer 运行环境"]
+    async fn personal_fm_retries_prefetch_after_failure() {
+        let mut backend = backend(vec![songlist(11, &[1])]);
+        backend.personal_fm_songs = vec![song(31), song(32)];
+        backend.song_urls.insert(31, String::from("http://localhost/31.mp3"));
+        let mut player = player_with(&backend).await;
+        player.set_play_mode(PlayMode::PersonalFm);
+        player.current_playlist = vec![song(1)];
+        player.current_song_index = Some(0);
+        player.current_song = Some(song(1));
+        player.play_state = PlayState::Ended;
+
+        // 未登录时预取失败，队列耗尽后保持私人FM，等待重试而不是停止
+        let logged_out = MemoryBackend::default();
+        player.auto_play(&logged_out).await.unwrap();
+        assert!(player.play_state == PlayState::Ended);
+        assert!(player.is_personal_fm());
+        assert!(player.personal_fm_retry_at.is_some());
+
+        // 等待重试期间不请求
+        player.auto_play(&backend).await.unwrap();
+        assert_eq!(player.current_playlist().len(), 1);
+
+        // 到达重试时间后预取成功，继续播放
+        player.personal_fm_retry_at = Some(Instant::now());
+        player.auto_play(&backend).await.unwrap();
+        assert!(player.play_state == PlayState::Playing);
+        assert_eq!(current_song_id(&player), Some(31));
+        assert_eq!(player.current_playlist().len(), 3);
+        assert!(player.personal_fm_retry_at.is_none());
+    }
+}
@@ -1,18 +1,327 @@
-use crate::{ncm_client, player};
-use anyhow::Result;
+use crate::config::Command;
+use crate::{cloud_upload, command_queue, ncm_client, player};
+use anyhow::{anyhow, Result};
+use log::error;
+use ncm_api::model::{Song, Songlist};
+use ncm_api::{NcmClient, USER_SONGLISTS_PAGE_SIZE};
+use ncm_play::PlayedTrack;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task;
+use tokio::time::sleep;
+
+/// 上传结束后在 bottom_bar 中保留结果的时长
+const UPLOAD_RESULT_DISPLAY_DURATION: Duration = Duration::from_secs(5);
 
 pub async fn init_songlists() -> Result<()> {
-    let ncm_client_guard = ncm_client.lock().await;
-    let mut player_guard = player.lock().await;
-    if let Ok(songlists) = ncm_client_guard.get_user_all_songlists().await {
+    // 网络请求只持有 ncm_client 的锁，不阻塞使用 player 的主循环
+    let (first_page, user_id) = {
+        let mut ncm_client_guard = ncm_client.lock().await;
+
+        // 缓存用户喜欢的歌曲，用于在播放列表中标记
+        if let Err(err) = ncm_client_guard.liked_song_ids().await {
+            error!("failed to get liked songs: {:?}", err);
+        }
+
+        // 先获取第一页歌单，剩余歌单在后台继续加载
+        let first_page = ncm_client_guard.get_user_songlists(0, USER_SONGLISTS_PAGE_SIZE).await;
+        (first_page, ncm_client_guard.login_account().map(|account| account.user_id))
+    };
+
+    if let Ok((songlists, has_more)) = first_page {
         let len = songlists.len();
 
-        player_guard.set_songlists(songlists);
+        // 每日推荐、云盘伪歌单固定在最前，默认仍播放用户的第一个歌单（我喜欢的音乐）
+        let mut all_songlists = vec![Songlist::daily_recommend(), Songlist::cloud_drive()];
+        let pseudo_count = all_songlists.len();
+        all_songlists.extend(songlists);
+
+        {
+            // 与主循环一致，先 player 后 ncm_client ，避免死锁
+            let mut player_guard = player.lock().await;
+            player_guard.set_songlists(all_songlists);
+
+            if len > 0 {
+                player_guard.switch_playlist(pseudo_count, &*ncm_client.lock().await).await?;
+            }
+        }
+
+        if has_more && len > 0 {
+            if let Some(user_id) = user_id {
+                task::spawn(load_remaining_songlists(user_id, len));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 从 `offset` 开始分页加载剩余的用户歌单，逐页追加到 player
+///
+/// 加载期间登出或切换账号（登录用户不再是 `user_id`）时丢弃结果并停止
+async fn load_remaining_songlists(user_id: u64, mut offset: usize) {
+    loop {
+        // 请求歌单时不持有 player 的锁；需要同时持有时与主循环一致，先 player 后 ncm_client ，避免死锁
+        let page = {
+            let ncm_client_guard = ncm_client.lock().await;
+            if !is_login_user(&ncm_client_guard, user_id) {
+                break;
+            }
+            ncm_client_guard.get_user_songlists(offset, USER_SONGLISTS_PAGE_SIZE).await
+        };
+
+        match page {
+            Ok((songlists, has_more)) => {
+                let len = songlists.len();
+
+                // 获取期间可能已登出，持有 player 锁后重新确认，保证不会追加到 reset 之后
+                let mut player_guard = player.lock().await;
+                if !is_login_user(&*ncm_client.lock().await, user_id) {
+                    break;
+                }
+                player_guard.append_songlists(songlists);
+                offset += len;
+
+                if !has_more || len == 0 {
+                    break;
+                }
+            },
+            Err(err) => {
+                error!("failed to load songlists from offset {}: {:?}", offset, err);
+                break;
+            },
+        }
+    }
+}
+
+fn is_login_user(ncm_client_guard: &NcmClient, user_id: u64) -> bool {
+    ncm_client_guard.login_account().is_some_and(|account| account.user_id == user_id)
+}
+
+/// 上报听歌记录，失败的记录保存到本地队列
+///
+/// 只在生成请求时持有 ncm_client 的锁，上报期间不阻塞主循环
+pub async fn scrobble_played_tracks(played_tracks: Vec<PlayedTrack>) {
+    for played_track in played_tracks {
+        let scrobble = ncm_client.lock().await.scrobble_or_enqueue(played_track.song_id, played_track.sourceid, played_track.played_seconds);
+        scrobble.await;
+    }
+}
+
+/// 重试本地队列中上报失败的听歌记录
+pub async fn retry_pending_scrobbles() {
+    let retry = ncm_client.lock().await.retry_pending_scrobbles();
+    retry.await;
+}
+
+/// 喜欢/取消喜欢歌曲，`like` 为 None 时切换当前状态
+pub async fn like_song(song_id: u64, like: Option<bool>) -> Result<()> {
+    let mut ncm_client_guard = ncm_client.lock().await;
+
+    let like = like.unwrap_or(!ncm_client_guard.is_liked_song(song_id));
+    ncm_client_guard.like_song(song_id, like).await?;
+
+    Ok(())
+}
+
+/// 喜欢/取消喜欢当前播放的歌曲，`like` 为 None 时切换当前状态
+pub async fn like_current_song(like: Option<bool>) -> Result<()> {
+    if let Some(song) = current_song().await {
+        like_song(song.id, like).await?;
+    }
+
+    Ok(())
+}
+
+/// 打开歌曲所属专辑的详情页，`song` 为 None 时使用当前播放的歌曲
+pub async fn view_album(song: Option<Song>) {
+    let song = match song {
+        Some(song) => Some(song),
+        None => current_song().await,
+    };
+
+    if let Some(song) = song {
+        command_queue.lock().await.push_back(Command::OpenAlbum(song.album_id));
+    }
+}
 
-        if len > 0 {
-            player_guard.switch_playlist(0, ncm_client_guard).await?;
+/// 打开歌曲所属歌手的主页，`song` 为 None 时使用当前播放的歌曲
+///
+/// 合作歌曲有多位歌手时，需由 `artist_index` 指定打开哪一位
+pub async fn view_artist(song: Option<Song>, artist_index: Option<usize>) -> Result<()> {
+    let song = match song {
+        Some(song) => Some(song),
+        None => current_song().await,
+    };
+
+    if let Some(song) = song {
+        let artist = match (artist_index, song.artists.len()) {
+            (_, 0) => return Err(anyhow!("`{}`没有歌手信息", song.name)),
+            (None, 1) => &song.artists[0],
+            (None, _) => {
+                let artists = song.artists.iter().enumerate().map(|(i, artist)| format!("{}.{}", i + 1, artist.name)).collect::<Vec<String>>().join(" ");
+                return Err(anyhow!("`{}`有多位歌手，请使用`artist <序号>`选择：{}", song.name, artists));
+            },
+            (Some(index), len) => song.artists.get(index).ok_or_else(|| anyhow!("`{}`只有{}位歌手", song.name, len))?,
+        };
+
+        if artist.id == 0 {
+            return Err(anyhow!("歌手`{}`没有主页", artist.name));
         }
+        command_queue.lock().await.push_back(Command::OpenArtist(artist.id));
     }
 
     Ok(())
 }
+
+/// 新建歌单
+pub async fn create_songlist(name: &str) -> Result<()> {
+    let songlist = ncm_client.lock().await.create_songlist(name).await?;
+    player.lock().await.add_songlist(songlist);
+
+    Ok(())
+}
+
+/// 删除歌单
+pub async fn delete_songlist(songlist: &Songlist) -> Result<()> {
+    if songlist.is_pseudo() {
+        return Err(anyhow!("`{}`不能删除", songlist.name));
+    }
+
+    ncm_client.lock().await.delete_songlist(songlist.id).await?;
+    player.lock().await.remove_songlist(songlist.id);
+
+    Ok(())
+}
+
+/// 重命名歌单
+pub async fn rename_songlist(songlist: &Songlist, name: &str) -> Result<()> {
+    if songlist.is_pseudo() {
+        return Err(anyhow!("`{}`不能重命名", songlist.name));
+    }
+
+    ncm_client.lock().await.rename_songlist(songlist.id, name).await?;
+    player.lock().await.rename_songlist(songlist.id, name);
+
+    Ok(())
+}
+
+/// 收藏/取消收藏歌单，`subscribe` 为 None 时切换当前状态
+pub async fn subscribe_songlist(songlist: &Songlist, subscribe: Option<bool>) -> Result<()> {
+    if songlist.is_pseudo() {
+        return Err(anyhow!("`{}`不能收藏", songlist.name));
+    }
+
+    let login_user_id = ncm_client.lock().await.login_account().map(|account| account.user_id);
+    if login_user_id == Some(songlist.creator_id) {
+        return Err(anyhow!("不能收藏/取消收藏自己创建的歌单"));
+    }
+
+    let is_subscribed = player.lock().await.songlists().iter().any(|s| s.id == songlist.id && s.subscribed);
+    let subscribe = subscribe.unwrap_or(!is_subscribed);
+    if subscribe == is_subscribed {
+        return Ok(());
+    }
+
+    ncm_client.lock().await.subscribe_songlist(songlist.id, subscribe).await?;
+
+    let mut player_guard = player.lock().await;
+    if subscribe {
+        player_guard.add_songlist(Songlist {
+            subscribed: true,
+            songs: Vec::new(),
+            ..songlist.clone()
+        });
+    } else {
+        player_guard.remove_songlist(songlist.id);
+    }
+
+    Ok(())
+}
+
+/// 将歌曲添加到指定名称的歌单，`song` 为 None 时使用当前播放的歌曲
+///
+/// 优先匹配同名歌单，否则匹配唯一包含该名称的歌单
+pub async fn add_to_songlist(songlist_name: &str, song: Option<Song>) -> Result<()> {
+    let song = match song {
+        Some(song) => song,
+        None => current_song().await.ok_or(anyhow!("请先选中要添加的歌曲"))?,
+    };
+
+    let songlist_id = {
+        let player_guard = player.lock().await;
+        let songlists: Vec<&Songlist> = player_guard.songlists().iter().filter(|songlist| !songlist.is_pseudo()).collect();
+
+        match songlists.iter().find(|songlist| songlist.name == songlist_name) {
+            Some(songlist) => songlist.id,
+            None => {
+                let matched: Vec<&&Songlist> = songlists.iter().filter(|songlist| songlist.name.contains(songlist_name)).collect();
+                match matched.as_slice() {
+                    [songlist] => songlist.id,
+                    [] => return Err(anyhow!("未找到歌单`{}`", songlist_name)),
+                    _ => return Err(anyhow!("有多个歌单包含`{}`，请输入完整的歌单名", songlist_name)),
+                }
+            },
+        }
+    };
+
+    ncm_client.lock().await.update_songlist_tracks(songlist_id, &[song.id], true).await?;
+    player.lock().await.add_songs_to_songlist(songlist_id, vec![song]);
+
+    Ok(())
+}
+
+/// 从歌单中删除歌曲
+pub async fn remove_from_songlist(songlist_id: u64, song: &Song) -> Result<()> {
+    // 播放列表不来自真实歌单（伪歌单、搜索结果等）时 id 为 0
+    if songlist_id == 0 {
+        return Err(anyhow!("当前播放列表不是可编辑的歌单"));
+    }
+    // 心动模式推荐的歌曲不在歌单中
+    if song.is_recommended {
+        return Err(anyhow!("`{}`是推荐歌曲，不在歌单中", song.name));
+    }
+
+    ncm_client.lock().await.update_songlist_tracks(songlist_id, &[song.id], false).await?;
+    player.lock().await.remove_songs_from_songlist(songlist_id, &[song.id]);
+
+    Ok(())
+}
+
+/// 开始上传本地音频文件到云盘（同一时间只能上传一个文件），上传在后台进行
+pub async fn upload_to_cloud(file_path: &Path) -> Result<()> {
+    if !file_path.is_file() {
+        return Err(anyhow!("文件`{}`不存在", file_path.display()));
+    }
+
+    let mut cloud_upload_guard = cloud_upload.lock().await;
+    if cloud_upload_guard.as_ref().is_some_and(|progress| progress.result().is_none()) {
+        return Err(anyhow!("已有文件正在上传，请等待上传完成"));
+    }
+
+    // 上传在返回的 future 中进行，不占用 ncm_client
+    let (progress, upload) = ncm_client.lock().await.upload_to_cloud(file_path)?;
+    *cloud_upload_guard = Some(progress.clone());
+    drop(cloud_upload_guard);
+
+    task::spawn(async move {
+        if let Err(err) = upload.await {
+            error!("failed to upload {} to cloud: {:?}", progress.file_name, err);
+        }
+
+        // 保留一段时间上传结果后清除（期间未开始新的上传）
+        sleep(UPLOAD_RESULT_DISPLAY_DURATION).await;
+        let mut cloud_upload_guard = cloud_upload.lock().await;
+        if cloud_upload_guard.as_ref().is_some_and(|current| Arc::ptr_eq(current, &progress)) {
+            *cloud_upload_guard = None;
+        }
+    });
+
+    Ok(())
+}
+
+/// 当前播放的歌曲
+pub async fn current_song() -> Option<Song> {
+    player.lock().await.current_song().clone()
+}
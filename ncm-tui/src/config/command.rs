@@ -1,23 +1,78 @@
 use crate::config::Command::SwitchPlayMode;
 use crate::config::ScreenEnum;
 use anyhow::{anyhow, Result};
+use ncm_api::model::{CommentSortType, QualityLevel};
 use ncm_play::PlayMode;
+use std::path::PathBuf;
 
 #[derive(Clone, Debug)]
 pub enum Command {
     Quit,
     GotoScreen(ScreenEnum),
     EnterCommand,
-    Logout,
+    /// 登出，参数为 true 时同时清除本地歌词缓存
+    Logout(bool),
     PlayOrPause,
     SetVolume(f64),
     SwitchPlayMode(PlayMode),
+    /// 设置优先获取的音质（不可用时自动回退到更低的音质）
+    SetQuality(QualityLevel),
     StartPlay,
+    /// 进入私人FM模式并开始播放
+    PersonalFm,
+    /// 私人FM中“不喜欢”当前歌曲并跳到下一首
+    FmTrash,
+    /// 以当前歌曲为种子进入心动模式并开始播放
+    Intelligence,
+    /// 以当前歌曲为种子开启相似歌曲电台（播放列表替换为相似歌曲，列表循环到末尾时自动追加）
+    SongRadio,
     NextSong,
     PrevSong,
     SearchForward(Vec<String>),
     SearchBackward(Vec<String>),
+    /// 进入输入框模式，之后的按键输入到当前 screen 聚焦的输入框
+    EnterInputMode,
+    /// 全局搜索歌曲
+    GlobalSearch(Vec<String>),
+    /// 切换全局搜索的类型（单曲/专辑/歌手/歌单/歌词）
+    SwitchSearchType,
     RefreshPlaylist,
+    /// 喜欢/取消喜欢所选歌曲（未选中歌曲时作用于当前播放的歌曲）
+    Like(bool),
+    /// 切换所选歌曲的喜欢状态（未选中歌曲时作用于当前播放的歌曲）
+    ToggleLike,
+    /// 查看所选歌曲（未选中歌曲时为当前播放的歌曲）所属的专辑
+    ViewAlbum,
+    /// 打开指定 id 的专辑详情页
+    OpenAlbum(u64),
+    /// 查看所选歌曲（未选中歌曲时为当前播放的歌曲）的歌手主页，多位歌手时需指定序号（从 0 开始）
+    ViewArtist(Option<usize>),
+    /// 打开指定 id 的歌手主页
+    OpenArtist(u64),
+    /// 新建指定名称的歌单
+    CreateSonglist(String),
+    /// 删除歌单页中选中的歌单，参数须与选中歌单的名称一致（防止误删）
+    DeleteSonglist(String),
+    /// 重命名歌单页中选中的歌单
+    RenameSonglist(String),
+    /// 将所选歌曲（未选中歌曲时为当前播放的歌曲）添加到指定名称的歌单
+    AddToSonglist(String),
+    /// 从所在歌单中删除所选歌曲
+    RemoveFromSonglist,
+    /// 收藏/取消收藏所选歌单（歌单页或歌单搜索结果）
+    Subscribe(bool),
+    /// 切换所选歌单的收藏状态（歌单页或歌单搜索结果）
+    ToggleSubscribe,
+    /// 在主页右侧切换显示歌词/当前歌曲的评论
+    ToggleComments,
+    /// 切换评论的排序方式（同时显示评论）
+    SortComments(CommentSortType),
+    /// 评论当前播放的歌曲
+    PostComment(String),
+    /// 回复评论面板中选中的评论
+    ReplyComment(String),
+    /// 上传本地音频文件到云盘
+    Upload(PathBuf),
 
     Down,
     Up,
@@ -44,13 +99,18 @@ impl Command {
             Some("screen") => match tokens.next() {
                 Some("1" | "main") => Ok(Self::GotoScreen(ScreenEnum::Main)),
                 Some("2" | "playlist" | "playlists") => Ok(Self::GotoScreen(ScreenEnum::Songlists)),
+                Some("3" | "search") => Ok(Self::GotoScreen(ScreenEnum::Search)),
                 Some("0" | "help") => Ok(Self::GotoScreen(ScreenEnum::Help)),
                 Some(other) => Err(anyhow!("screen: Invalid screen identifier: {}", other)),
                 None => Err(anyhow!("screen: Missing argument SCREEN_ID")),
             },
             Some("h" | "help") => Ok(Self::GotoScreen(ScreenEnum::Help)),
             Some("l" | "login") => Ok(Self::GotoScreen(ScreenEnum::Login)),
-            Some("logout") => Ok(Self::Logout),
+            Some("logout") => match tokens.next() {
+                Some("purge") => Ok(Self::Logout(true)),
+                Some(other) => Err(anyhow!("logout: Invalid argument '{}'", other)),
+                None => Ok(Self::Logout(false)),
+            },
             Some("vol" | "volume") => match tokens.next() {
                 Some(num) => {
                     if let Ok(vol) = num.parse::<f64>() {
@@ -70,14 +130,116 @@ impl Command {
                 Some(other) => Err(anyhow!("switch: Invalid play mode identifier: {}", other)),
                 None => Err(anyhow!("switch: Missing argument PLAY_MODE")),
             },
+            Some("quality") => match tokens.next() {
+                Some(level) => match QualityLevel::from_level(level) {
+                    Some(quality_level) => Ok(Self::SetQuality(quality_level)),
+                    None => Err(anyhow!("quality: Invalid quality level: {}", level)),
+                },
+                None => Err(anyhow!("quality: Missing argument LEVEL")),
+            },
             Some("next") => Ok(Self::NextSong),
             Some("prev" | "previous") => Ok(Self::PrevSong),
             Some("start") => Ok(Self::StartPlay),
+            Some("fm") => match tokens.next() {
+                Some("trash" | "dislike") => Ok(Self::FmTrash),
+                Some(other) => Err(anyhow!("fm: Invalid argument '{}'", other)),
+                None => Ok(Self::PersonalFm),
+            },
+            Some("hb" | "heartbeat" | "intelligence") => Ok(Self::Intelligence),
+            Some("radio") => Ok(Self::SongRadio),
             Some("where") => match tokens.next() {
                 Some("this") => Ok(Self::WhereIsThisSong),
                 Some(other) => Err(anyhow!("where: Invalid argument '{}'", other)),
                 None => Err(anyhow!("where: Missing argument")),
             },
+            Some("search") => {
+                let keywords: Vec<String> = tokens.map(|keyword| keyword.to_string()).collect();
+                if keywords.is_empty() {
+                    Err(anyhow!("search: Missing argument KEYWORDS"))
+                } else {
+                    Ok(Self::GlobalSearch(keywords))
+                }
+            },
+            Some("like") => Ok(Self::Like(true)),
+            Some("unlike") => Ok(Self::Like(false)),
+            Some("album") => Ok(Self::ViewAlbum),
+            Some("artist") => match tokens.next() {
+                Some(num) => match num.parse::<usize>() {
+                    Ok(index) if index > 0 => Ok(Self::ViewArtist(Some(index - 1))),
+                    _ => Err(anyhow!("artist: Invalid argument INDEX")),
+                },
+                None => Ok(Self::ViewArtist(None)),
+            },
+            Some("songlist") => match tokens.next() {
+                Some("new" | "create") => {
+                    let name = raw_argument(cmd_str, 2).to_string();
+                    if name.is_empty() {
+                        Err(anyhow!("songlist new: Missing argument NAME"))
+                    } else {
+                        Ok(Self::CreateSonglist(name))
+                    }
+                },
+                Some("delete" | "rm") => {
+                    let name = raw_argument(cmd_str, 2).to_string();
+                    if name.is_empty() {
+                        Err(anyhow!("songlist delete: Missing argument NAME"))
+                    } else {
+                        Ok(Self::DeleteSonglist(name))
+                    }
+                },
+                Some("rename") => {
+                    let name = raw_argument(cmd_str, 2).to_string();
+                    if name.is_empty() {
+                        Err(anyhow!("songlist rename: Missing argument NAME"))
+                    } else {
+                        Ok(Self::RenameSonglist(name))
+                    }
+                },
+                Some(other) => Err(anyhow!("songlist: Invalid argument '{}'", other)),
+                None => Err(anyhow!("songlist: Missing argument new / delete / rename")),
+            },
+            Some("add-to") => {
+                let name = raw_argument(cmd_str, 1).to_string();
+                if name.is_empty() {
+                    Err(anyhow!("add-to: Missing argument SONGLIST"))
+                } else {
+                    Ok(Self::AddToSonglist(name))
+                }
+            },
+            Some("remove") => Ok(Self::RemoveFromSonglist),
+            Some("subscribe" | "sub") => Ok(Self::Subscribe(true)),
+            Some("unsubscribe" | "unsub") => Ok(Self::Subscribe(false)),
+            Some("comments") => match tokens.next() {
+                Some("rec" | "recommend") => Ok(Self::SortComments(CommentSortType::Recommend)),
+                Some("hot") => Ok(Self::SortComments(CommentSortType::Hot)),
+                Some("new" | "latest") => Ok(Self::SortComments(CommentSortType::Time)),
+                Some(other) => Err(anyhow!("comments: Invalid sort type: {}", other)),
+                None => Ok(Self::ToggleComments),
+            },
+            Some("comment") => {
+                let content = raw_argument(cmd_str, 1).to_string();
+                if content.is_empty() {
+                    Err(anyhow!("comment: Missing argument CONTENT"))
+                } else {
+                    Ok(Self::PostComment(content))
+                }
+            },
+            Some("reply") => {
+                let content = raw_argument(cmd_str, 1).to_string();
+                if content.is_empty() {
+                    Err(anyhow!("reply: Missing argument CONTENT"))
+                } else {
+                    Ok(Self::ReplyComment(content))
+                }
+            },
+            Some("upload") => {
+                let path = raw_argument(cmd_str, 1);
+                match path.strip_prefix("~/") {
+                    _ if path.is_empty() => Err(anyhow!("upload: Missing argument PATH")),
+                    Some(relative_path) => Ok(Self::Upload(dirs_next::home_dir().unwrap_or_default().join(relative_path))),
+                    None => Ok(Self::Upload(PathBuf::from(path))),
+                }
+            },
             Some("top") => Ok(Self::GoToTop),
             Some("bottom") => Ok(Self::GoToBottom),
             Some("/") => {
@@ -99,3 +261,13 @@ impl Command {
         }
     }
 }
+
+/// 跳过命令开头的 `skip` 个词，返回其后的原始参数（保留其中的连续空格，用于歌单名、评论内容和路径）
+fn raw_argument(cmd_str: &str, skip: usize) -> &str {
+    let mut rest = cmd_str.trim_start();
+    for _ in 0..skip {
+        rest = rest.trim_start_matches(|c: char| !c.is_whitespace()).trim_start();
+    }
+
+    rest
+}
@@ -15,6 +15,8 @@ pub struct Path {
     pub settings: PathBuf,
     pub login_cookie: PathBuf,
     pub lyrics: PathBuf,
+    pub covers: PathBuf,
+    pub scrobble_queue: PathBuf,
 }
 
 impl Path {
@@ -40,11 +42,18 @@ impl Path {
 
         let login_cookie = data.clone().join("cookies");
 
+        let scrobble_queue = data.clone().join("scrobble_queue.json");
+
         let lyrics = cache.clone().join("lyrics");
         if !lyrics.exists() {
             fs::create_dir_all(&lyrics).expect("Couldn't create lyrics dir.");
         }
 
+        let covers = cache.clone().join("covers");
+        if !covers.exists() {
+            fs::create_dir_all(&covers).expect("Couldn't create covers dir.");
+        }
+
         Self {
             data,
             config,
@@ -53,6 +62,8 @@ impl Path {
             settings,
             login_cookie,
             lyrics,
+            covers,
+            scrobble_queue,
         }
     }
 }
@@ -7,4 +7,20 @@ pub const ITEM_SELECTED_STYLE: Style = Style::new().bg(tailwind::RED.c400).add_m
 
 pub const LYRIC_FOCUSED_STYLE: Style = Style::new().fg(tailwind::RED.c600).add_modifier(Modifier::BOLD);
 
+/// 逐字歌词当前行中尚未唱到的字
+pub const LYRIC_UNSUNG_STYLE: Style = Style::new().fg(tailwind::SLATE.c400).add_modifier(Modifier::BOLD);
+
+/// 逐字歌词当前行的高亮（颜色由每个字的样式决定）
+pub const LYRIC_KARAOKE_FOCUSED_STYLE: Style = Style::new().add_modifier(Modifier::BOLD);
+
+pub const LIKED_MARK_STYLE: Style = Style::new().fg(tailwind::RED.c500);
+
+pub const RECOMMEND_REASON_STYLE: Style = Style::new().fg(tailwind::SLATE.c500).add_modifier(Modifier::ITALIC);
+
+pub const SUBSCRIBED_BADGE_STYLE: Style = Style::new().fg(tailwind::AMBER.c500).add_modifier(Modifier::BOLD);
+
+pub const COMMENT_NICKNAME_STYLE: Style = Style::new().fg(tailwind::SKY.c500).add_modifier(Modifier::BOLD);
+
+pub const COMMENT_META_STYLE: Style = Style::new().fg(tailwind::SLATE.c500);
+
 pub const TABLE_HEADER_STYLE: Style = Style::new().fg(tailwind::WHITE).bg(tailwind::RED.c300);
@@ -1,16 +1,37 @@
+use ncm_api::model::QualityLevel;
 use serde::{Deserialize, Serialize};
 
 pub enum AppMode {
     Normal,
     CommandLine,
     Search(Vec<String>),
+    /// 在 screen 内的输入框中输入（如登录页的账号密码）
+    Input,
 }
 
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy)]
 pub enum ScreenEnum {
     Main,
     Songlists,
+    Search,
+    Album,
+    Artist,
     Login,
     Help,
     Launch,
 }
+
+/// 音质等级的显示名称
+pub fn quality_level_label(quality_level: QualityLevel) -> &'static str {
+    match quality_level {
+        QualityLevel::Standard => "标准",
+        QualityLevel::Higher => "较高",
+        QualityLevel::Exhigh => "极高",
+        QualityLevel::Lossless => "无损",
+        QualityLevel::Hires => "Hi-Res",
+        QualityLevel::Jyeffect => "高清环绕声",
+        QualityLevel::Sky => "沉浸环绕声",
+        QualityLevel::Dolby => "杜比全景声",
+        QualityLevel::Jymaster => "超清母带",
+    }
+}
@@ -8,6 +8,8 @@ use anyhow::Result;
 use crossterm::terminal::{enable_raw_mode, EnterAlternateScreen};
 use crossterm::{event, execute};
 use lazy_static::lazy_static;
+use log::error;
+use ncm_api::model::UploadProgress;
 use ncm_api::NcmClient;
 use ncm_play::Player;
 use ratatui::backend::CrosstermBackend;
@@ -28,10 +30,14 @@ lazy_static! {
         path_config.api_program.clone(),
         path_config.login_cookie.clone(),
         path_config.lyrics.clone(),
+        path_config.covers.clone(),
         path_config.settings.clone(),
+        path_config.scrobble_queue.clone(),
     )));
     static ref player: Arc<Mutex<Player>> = Arc::new(Mutex::new(Player::new()));
     static ref command_queue: Arc<Mutex<VecDeque<Command>>> = Arc::new(Mutex::new(VecDeque::new()));
+    // 正在进行（或刚结束）的云盘上传，由 bottom_bar 显示进度
+    static ref cloud_upload: Arc<Mutex<Option<Arc<UploadProgress>>>> = Arc::new(Mutex::new(None));
 }
 
 #[tokio::main]
@@ -52,7 +58,13 @@ async fn main() -> Result<()> {
         sleep(Duration::from_secs(1)).await; // 给启动帧留缓冲
 
         if ncm_client_2.lock().await.check_api().await {
-            if ncm_client_2.lock().await.try_cookie_login().await.unwrap_or(false) {
+            // 网络异常或响应解析失败时按未登录处理，记录原因
+            let cookie_login_result = ncm_client_2.lock().await.try_cookie_login().await;
+            if let Err(err) = cookie_login_result.as_ref() {
+                error!("failed to login with cookie: {:?}", err);
+            }
+
+            if cookie_login_result.unwrap_or(false) {
                 app_2.lock().await.init_after_login().await.expect("Couldn't initialize application");
             } else {
                 app_2.lock().await.init_after_no_login().await;
@@ -62,7 +74,17 @@ async fn main() -> Result<()> {
 
     loop {
         // 检查播放情况
-        player.lock().await.auto_play(ncm_client.lock().await).await?;
+        // 先释放 player 和 ncm_client 的锁，再获取 app 的锁，避免与初始化线程死锁
+        let auto_play_result = player.lock().await.auto_play(&*ncm_client.lock().await).await;
+        if let Err(err) = auto_play_result {
+            app.lock().await.show_error(err);
+        }
+
+        // 上报播放结束或被切换的歌曲
+        let played_tracks = player.lock().await.take_played_tracks();
+        if !played_tracks.is_empty() {
+            task::spawn(actions::scrobble_played_tracks(played_tracks));
+        }
 
         // 根据 Controller 流程，先执行 update_model()，再执行 handle_event()
         app.lock().await.update_model().await?;
@@ -1,12 +1,12 @@
 use crate::config::LOGO_LINES;
-use crate::ui::widget::{BottomBar, CommandLine};
+use crate::ui::widget::{clear_terminal_graphics, BottomBar, CommandLine, GraphicsProtocol};
 use crate::{
     actions, command_queue,
-    config::{AppMode, Command, ScreenEnum},
+    config::{quality_level_label, AppMode, Command, ScreenEnum},
     ncm_client, player,
     ui::{screen::*, Controller},
 };
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use crossterm::event::KeyModifiers;
 use crossterm::{
     event,
@@ -14,11 +14,13 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, LeaveAlternateScreen},
 };
-use log::debug;
+use log::{debug, error};
+use ncm_api::NcmError;
 use ratatui::prelude::*;
 use ratatui::style::palette::tailwind;
 use ratatui::widgets::Paragraph;
 use std::io::Stdout;
+use tokio::task;
 
 pub struct App<'a> {
     // model
@@ -29,6 +31,9 @@ pub struct App<'a> {
     // view
     main_screen: MainScreen<'a>,
     songlists_screen: SonglistsScreen<'a>,
+    search_screen: SearchScreen<'a>,
+    album_screen: AlbumScreen<'a>,
+    artist_screen: ArtistScreen<'a>,
     login_screen: LoginScreen<'a>,
     help_screen: HelpScreen<'a>,
     command_line: CommandLine<'a>,
@@ -50,6 +55,9 @@ impl<'a> App<'a> {
             need_re_update_view: true,
             main_screen: MainScreen::new(&normal_style),
             songlists_screen: SonglistsScreen::new(&normal_style),
+            search_screen: SearchScreen::new(&normal_style),
+            album_screen: AlbumScreen::new(&normal_style),
+            artist_screen: ArtistScreen::new(&normal_style),
             login_screen: LoginScreen::new(&normal_style),
             help_screen: HelpScreen::new(&normal_style),
             command_line: CommandLine::new(),
@@ -122,6 +130,9 @@ impl<'a> App<'a> {
         // 初始化，获取用户所有歌单（缩略）和 `用户喜欢的音乐` 歌单（详细信息）
         actions::init_songlists().await?;
 
+        // 重试上次未能上报的听歌记录
+        task::spawn(actions::retry_pending_scrobbles());
+
         // 提醒 main_screen 更新 playlist
         command_queue.lock().await.push_back(Command::RefreshPlaylist);
 
@@ -137,6 +148,18 @@ impl<'a> App<'a> {
         self.command_line.set_content("按下`:`进行命令输入，输入`login`命令进入登录页面");
     }
 
+    /// 在 command_line 中显示错误信息（不退出程序）
+    pub fn show_error(&mut self, err: anyhow::Error) {
+        error!("{:?}", err);
+
+        let msg = match err.downcast_ref::<NcmError>() {
+            Some(NcmError::NotLoggedIn) => String::from("未登录，请先使用`login`命令登录"),
+            Some(NcmError::CookieExpired) => String::from("登录已过期，请使用`logout`命令登出后重新登录"),
+            _ => err.to_string(),
+        };
+        self.command_line.set_content(&msg);
+    }
+
     pub fn restore_terminal(&mut self) -> Result<()> {
         disable_raw_mode()?;
         execute!(self.terminal.backend_mut(), LeaveAlternateScreen)?;
@@ -150,12 +173,22 @@ impl<'a> App<'a> {
 impl<'a> App<'a> {
     pub async fn update_model(&mut self) -> Result<()> {
         // screen
-        self.need_re_update_view = match self.current_screen {
-            ScreenEnum::Help => false,
-            ScreenEnum::Login => self.update_login_model().await?,
-            ScreenEnum::Main => self.main_screen.update_model().await?,
-            ScreenEnum::Songlists => self.songlists_screen.update_model().await?,
-            _ => false,
+        let update_result = match self.current_screen {
+            ScreenEnum::Help => Ok(false),
+            ScreenEnum::Login => self.update_login_model().await,
+            ScreenEnum::Main => self.main_screen.update_model().await,
+            ScreenEnum::Songlists => self.songlists_screen.update_model().await,
+            ScreenEnum::Search => self.search_screen.update_model().await,
+            ScreenEnum::Album => self.album_screen.update_model().await,
+            ScreenEnum::Artist => self.artist_screen.update_model().await,
+            _ => Ok(false),
+        };
+        self.need_re_update_view = match update_result {
+            Ok(need_re_update_view) => need_re_update_view,
+            Err(err) => {
+                self.show_error(err);
+                true
+            },
         };
 
         // bottom_bar
@@ -196,6 +229,23 @@ impl<'a> App<'a> {
                     },
                     (AppMode::Search(_), _) => {},
 
+                    // Input 模式
+                    // esc 结束输入，enter / tab 结束输入并聚焦到下一项，其余按键输入到 screen 内的输入框
+                    (AppMode::Input, KeyCode::Esc) => {
+                        self.back_to_normal_mode();
+                        command_queue.lock().await.push_back(Command::Esc);
+                    },
+                    (AppMode::Input, KeyCode::Enter | KeyCode::Tab) => {
+                        self.back_to_normal_mode();
+                        command_queue.lock().await.push_back(Command::Down);
+                    },
+                    (AppMode::Input, _) => {
+                        if self.current_screen == ScreenEnum::Login {
+                            self.login_screen.input(key_event);
+                            self.need_re_update_view = true;
+                        }
+                    },
+
                     // CommandLine 模式
                     (AppMode::CommandLine, KeyCode::Enter) => {
                         self.parse_command().await;
@@ -243,10 +293,11 @@ impl<'a> App<'a> {
                 Command::EnterCommand => {
                     self.switch_to_command_line_mode();
                 },
-                Command::Logout => {
-                    self.login_screen = LoginScreen::new(&self.normal_style);
-                    // TODO: 清除 cache
-                    ncm_client.lock().await.logout().await?;
+                Command::EnterInputMode => {
+                    self.switch_to_input_mode();
+                },
+                Command::Logout(purge_lyrics_cache) => {
+                    self.logout(purge_lyrics_cache).await?;
                 },
                 Command::PlayOrPause => {
                     player.lock().await.play_or_pause();
@@ -257,16 +308,49 @@ impl<'a> App<'a> {
                 Command::SwitchPlayMode(play_mode) => {
                     player.lock().await.set_play_mode(play_mode);
                 },
+                Command::SetQuality(quality_level) => {
+                    ncm_client.lock().await.set_preferred_quality(quality_level);
+                    self.command_line.set_content(&format!("音质已设为{}，从下一首歌曲开始生效", quality_level_label(quality_level)));
+                },
                 Command::StartPlay => {
-                    if let Err(e) = player.lock().await.start_play(ncm_client.lock().await).await {
-                        self.command_line.set_content(e.to_string().as_str());
+                    if let Err(e) = player.lock().await.start_play(&*ncm_client.lock().await).await {
+                        self.show_error(e);
+                    }
+                },
+                Command::PersonalFm | Command::Intelligence | Command::SongRadio => {
+                    let mut player_guard = player.lock().await;
+                    let start_result = match cmd {
+                        Command::PersonalFm => player_guard.start_personal_fm(&*ncm_client.lock().await).await,
+                        Command::Intelligence => player_guard.start_intelligence(&*ncm_client.lock().await).await,
+                        _ => player_guard.start_song_radio(&*ncm_client.lock().await).await,
+                    };
+                    drop(player_guard);
+
+                    match start_result {
+                        Ok(_) => {
+                            // 返回 main_screen ，刷新播放列表显示
+                            let mut command_queue_guard = command_queue.lock().await;
+                            command_queue_guard.push_back(Command::GotoScreen(ScreenEnum::Main));
+                            command_queue_guard.push_back(Command::RefreshPlaylist);
+                            command_queue_guard.push_back(Command::WhereIsThisSong);
+                        },
+                        Err(e) => self.show_error(e),
+                    }
+                },
+                Command::FmTrash => {
+                    if let Err(e) = player.lock().await.fm_trash_current_song(&*ncm_client.lock().await).await {
+                        self.show_error(e);
                     }
                 },
                 Command::NextSong => {
-                    player.lock().await.play_next_song_now(ncm_client.lock().await).await?;
+                    if let Err(e) = player.lock().await.play_next_song_now(&*ncm_client.lock().await).await {
+                        self.show_error(e);
+                    }
                 },
                 Command::PrevSong => {
-                    player.lock().await.play_prev_song_now(ncm_client.lock().await).await?;
+                    if let Err(e) = player.lock().await.play_prev_song_now(&*ncm_client.lock().await).await {
+                        self.show_error(e);
+                    }
                 },
                 Command::SearchForward(search_keywords) => {
                     self.switch_to_search_mode(search_keywords);
@@ -274,6 +358,39 @@ impl<'a> App<'a> {
                 Command::SearchBackward(search_keywords) => {
                     self.switch_to_search_mode(search_keywords);
                 },
+                Command::GlobalSearch(_) => {
+                    self.switch_screen(ScreenEnum::Search).await;
+                    self.command_line.handle_event(Command::GotoScreen(ScreenEnum::Search)).await?;
+                },
+                Command::OpenAlbum(_) => {
+                    self.switch_screen(ScreenEnum::Album).await;
+                    self.command_line.handle_event(Command::GotoScreen(ScreenEnum::Album)).await?;
+                },
+                Command::OpenArtist(_) => {
+                    self.switch_screen(ScreenEnum::Artist).await;
+                    self.command_line.handle_event(Command::GotoScreen(ScreenEnum::Artist)).await?;
+                },
+                Command::Upload(file_path) => match actions::upload_to_cloud(&file_path).await {
+                    Ok(_) => self.command_line.set_content(&format!("开始上传`{}`到云盘，进度显示在底栏", file_path.display())),
+                    Err(e) => self.show_error(e),
+                },
+                Command::CreateSonglist(name) => {
+                    if let Err(e) = actions::create_songlist(&name).await {
+                        self.show_error(e);
+                    }
+                },
+                // 删除/重命名只作用于歌单页中选中的歌单
+                Command::DeleteSonglist(_) | Command::RenameSonglist(_) if self.current_screen != ScreenEnum::Songlists => {
+                    self.show_error(anyhow!("请在歌单页中选中要编辑的歌单"));
+                },
+                // 评论面板位于 main_screen ，先切换过去再向下传递
+                Command::ToggleComments | Command::SortComments(_) | Command::PostComment(_) if self.current_screen != ScreenEnum::Main => {
+                    self.switch_screen(ScreenEnum::Main).await;
+                    self.command_line.handle_event(Command::GotoScreen(ScreenEnum::Main)).await?;
+                },
+                Command::ReplyComment(_) if self.current_screen != ScreenEnum::Main => {
+                    self.show_error(anyhow!("请在主页的评论面板中选中要回复的评论"));
+                },
                 _ => {},
             }
 
@@ -292,17 +409,46 @@ impl<'a> App<'a> {
                     | Command::GoToBottom
                     | Command::SearchForward(_)
                     | Command::SearchBackward(_)
+                    | Command::GlobalSearch(_)
+                    | Command::SwitchSearchType
+                    | Command::Like(_)
+                    | Command::ToggleLike
+                    | Command::ViewAlbum
+                    | Command::OpenAlbum(_)
+                    | Command::ViewArtist(_)
+                    | Command::OpenArtist(_)
+                    | Command::DeleteSonglist(_)
+                    | Command::RenameSonglist(_)
+                    | Command::AddToSonglist(_)
+                    | Command::RemoveFromSonglist
+                    | Command::Subscribe(_)
+                    | Command::ToggleSubscribe
+                    | Command::ToggleComments
+                    | Command::SortComments(_)
+                    | Command::PostComment(_)
+                    | Command::ReplyComment(_)
                     | Command::RefreshPlaylist
             ) {
                 // 先 update_model(), 再 handle_event()
                 // 取或值
                 // 若写成 self.need_re_update_view = self.need_re_update_view || match ... {} ，match块内的方法可能不被执行
-                self.need_re_update_view = match self.current_screen {
-                    ScreenEnum::Main => self.main_screen.handle_event(cmd).await?,
-                    ScreenEnum::Songlists => self.songlists_screen.handle_event(cmd).await?,
-                    ScreenEnum::Login => self.login_screen.handle_event(cmd).await?,
-                    ScreenEnum::Help => self.help_screen.handle_event(cmd).await?,
-                    _ => false,
+                // 出错时在 command_line 中显示错误信息，不退出程序
+                let handle_result = match self.current_screen {
+                    ScreenEnum::Main => self.main_screen.handle_event(cmd).await,
+                    ScreenEnum::Songlists => self.songlists_screen.handle_event(cmd).await,
+                    ScreenEnum::Search => self.search_screen.handle_event(cmd).await,
+                    ScreenEnum::Album => self.album_screen.handle_event(cmd).await,
+                    ScreenEnum::Artist => self.artist_screen.handle_event(cmd).await,
+                    ScreenEnum::Login => self.login_screen.handle_event(cmd).await,
+                    ScreenEnum::Help => self.help_screen.handle_event(cmd).await,
+                    _ => Ok(false),
+                };
+                self.need_re_update_view = match handle_result {
+                    Ok(need_re_update_view) => need_re_update_view,
+                    Err(err) => {
+                        self.show_error(err);
+                        true
+                    },
                 } || self.need_re_update_view;
             }
         }
@@ -318,6 +464,9 @@ impl<'a> App<'a> {
                 ScreenEnum::Login => self.login_screen.update_view(&self.normal_style),
                 ScreenEnum::Main => self.main_screen.update_view(&self.normal_style),
                 ScreenEnum::Songlists => self.songlists_screen.update_view(&self.normal_style),
+                ScreenEnum::Search => self.search_screen.update_view(&self.normal_style),
+                ScreenEnum::Album => self.album_screen.update_view(&self.normal_style),
+                ScreenEnum::Artist => self.artist_screen.update_view(&self.normal_style),
                 _ => {},
             }
         }
@@ -351,6 +500,9 @@ impl<'a> App<'a> {
                 ScreenEnum::Login => self.login_screen.draw(frame, chunks[0]),
                 ScreenEnum::Main => self.main_screen.draw(frame, chunks[0]),
                 ScreenEnum::Songlists => self.songlists_screen.draw(frame, chunks[0]),
+                ScreenEnum::Search => self.search_screen.draw(frame, chunks[0]),
+                ScreenEnum::Album => self.album_screen.draw(frame, chunks[0]),
+                ScreenEnum::Artist => self.artist_screen.draw(frame, chunks[0]),
                 _ => {},
             }
 
@@ -388,11 +540,16 @@ impl<'a> App<'a> {
             KeyCode::Char('h') => Command::PrevPanel,
             KeyCode::Char('1') => Command::GotoScreen(ScreenEnum::Main),
             KeyCode::Char('2') => Command::GotoScreen(ScreenEnum::Songlists),
+            KeyCode::Char('3') => Command::GotoScreen(ScreenEnum::Search),
             KeyCode::Char('0') => Command::GotoScreen(ScreenEnum::Help),
             KeyCode::F(1) => Command::GotoScreen(ScreenEnum::Help),
             KeyCode::Char('.') | KeyCode::Char('。') => Command::NextSong,
             KeyCode::Char(',') | KeyCode::Char('，') => Command::PrevSong,
             KeyCode::Char(':') | KeyCode::Char('：') => Command::EnterCommand,
+            KeyCode::Char('t') => Command::SwitchSearchType,
+            KeyCode::Char('f') => Command::ToggleLike,
+            KeyCode::Char('s') => Command::ToggleSubscribe,
+            KeyCode::Char('c') => Command::ToggleComments,
             KeyCode::Char('/') => {
                 self.switch_to_search_input_mode();
                 self.command_line.set_content("/ ");
@@ -443,6 +600,11 @@ impl<'a> App<'a> {
         self.command_line.set_to_search_mode()
     }
 
+    fn switch_to_input_mode(&mut self) {
+        self.current_mode = AppMode::Input;
+        self.command_line.set_to_input_mode();
+    }
+
     /// 输入搜索命令时特殊的混合模式
     fn switch_to_search_input_mode(&mut self) {
         self.current_mode = AppMode::CommandLine;
@@ -462,6 +624,39 @@ impl<'a> App<'a> {
         }
     }
 
+    /// 登出，清除账号、播放状态和各 screen 中缓存的歌单/歌曲，并返回未登录状态的 main_screen
+    async fn logout(&mut self, purge_lyrics_cache: bool) -> Result<()> {
+        let mut ncm_client_guard = ncm_client.lock().await;
+        if !ncm_client_guard.is_login() {
+            drop(ncm_client_guard);
+            self.command_line.set_content("当前未登录");
+            return Ok(());
+        }
+
+        ncm_client_guard.logout().await?;
+        if purge_lyrics_cache {
+            if let Err(err) = ncm_client_guard.clear_lyrics_cache() {
+                error!("failed to clear lyrics cache: {:?}", err);
+            }
+        }
+        drop(ncm_client_guard);
+
+        player.lock().await.reset();
+
+        self.main_screen = MainScreen::new(&self.normal_style);
+        self.songlists_screen = SonglistsScreen::new(&self.normal_style);
+        self.search_screen = SearchScreen::new(&self.normal_style);
+        self.album_screen = AlbumScreen::new(&self.normal_style);
+        self.artist_screen = ArtistScreen::new(&self.normal_style);
+        self.login_screen = LoginScreen::new(&self.normal_style);
+
+        self.switch_screen(ScreenEnum::Main).await;
+        self.command_line.handle_event(Command::GotoScreen(ScreenEnum::Main)).await?;
+        self.command_line.set_content("已登出，当前未登录。输入`login`命令重新登录");
+
+        Ok(())
+    }
+
     async fn switch_screen(&mut self, to_screen: ScreenEnum) {
         // 已登录状态不能切换到 login_screen
         let ncm_client_guard = ncm_client.lock().await;
@@ -493,6 +688,15 @@ impl<'a> App<'a> {
             _ => {},
         }
 
+        // 以终端图形协议绘制的封面不会被其他屏幕的文字覆盖，离开 main_screen 时清除并整屏重绘
+        let graphics_protocol = GraphicsProtocol::detect();
+        if self.current_screen == ScreenEnum::Main && to_screen != ScreenEnum::Main && graphics_protocol.is_graphics() {
+            clear_terminal_graphics(graphics_protocol);
+            if let Err(err) = self.terminal.clear() {
+                error!("failed to clear terminal: {:?}", err);
+            }
+        }
+
         self.need_re_update_view = true;
         self.current_screen = to_screen;
     }
@@ -1,9 +1,19 @@
+mod artist_albums_panel;
+mod comments_panel;
+mod cover_panel;
 mod lyric_panel;
 mod playlist_panel;
+mod search_panel;
+mod similar_artists_panel;
 mod songlist_candidates_panel;
 
+pub use artist_albums_panel::*;
+pub use comments_panel::*;
+pub use cover_panel::*;
 pub use lyric_panel::*;
 pub use playlist_panel::*;
+pub use search_panel::*;
+pub use similar_artists_panel::*;
 pub use songlist_candidates_panel::*;
 
 #[derive(PartialEq)]
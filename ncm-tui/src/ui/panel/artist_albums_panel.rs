@@ -0,0 +1,177 @@
+use crate::config::style::*;
+use crate::config::Command;
+use crate::ncm_client;
+use crate::ui::panel::PanelFocusedStatus;
+use crate::ui::Controller;
+use ncm_api::model::Album;
+use ratatui::layout::{Constraint, Margin, Rect};
+use ratatui::prelude::Style;
+use ratatui::style::palette::tailwind;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Cell, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table, TableState};
+use ratatui::Frame;
+
+/// 每页专辑数量
+const ALBUMS_PAGE_SIZE: usize = 30;
+
+pub struct ArtistAlbumsPanel<'a> {
+    // model
+    pub focused_status: PanelFocusedStatus, // 聚焦状态交给父 screen 管理，面板自身只读不写
+    //
+    artist_id: u64,
+    albums: Vec<Album>,
+    has_more: bool, // 是否还有下一页
+    albums_table_rows: Vec<Row<'a>>,
+    albums_table_state: TableState,
+    scrollbar_state: ScrollbarState,
+
+    // view
+    albums_table: Table<'a>,
+}
+
+impl<'a> ArtistAlbumsPanel<'a> {
+    pub fn new(focused_status: PanelFocusedStatus) -> Self {
+        Self {
+            focused_status,
+            artist_id: 0,
+            albums: Vec::new(),
+            has_more: false,
+            albums_table_rows: Vec::new(),
+            albums_table_state: TableState::new(),
+            scrollbar_state: ScrollbarState::new(0),
+            albums_table: Table::default(),
+        }
+    }
+}
+
+impl<'a> ArtistAlbumsPanel<'a> {
+    /// 切换到指定歌手，并获取第一页专辑
+    pub async fn load_artist_albums(&mut self, artist_id: u64) -> anyhow::Result<()> {
+        self.artist_id = artist_id;
+        self.albums = Vec::new();
+        self.albums_table_rows = Vec::new();
+        self.has_more = true;
+
+        self.load_next_page().await?;
+
+        // 防止悬空
+        self.albums_table_state.select(None);
+        self.scrollbar_state = ScrollbarState::new(self.albums_table_rows.len());
+
+        Ok(())
+    }
+
+    pub fn get_selected_album(&self) -> Option<Album> {
+        self.albums_table_state.selected().and_then(|selected| self.albums.get(selected)).cloned()
+    }
+
+    /// 获取下一页专辑，追加到已有专辑之后
+    async fn load_next_page(&mut self) -> anyhow::Result<()> {
+        if !self.has_more {
+            return Ok(());
+        }
+
+        let (albums, has_more) = ncm_client.lock().await.get_artist_albums(self.artist_id, self.albums.len(), ALBUMS_PAGE_SIZE).await?;
+
+        self.has_more = has_more;
+        self.albums_table_rows.extend(albums.iter().map(|album| {
+            Row::from_iter(vec![
+                Cell::new(album.name.clone()),
+                Cell::new(album.publish_date()),
+                Cell::new(format!("{:>6}", album.songs_count)),
+            ])
+        }));
+        self.albums.extend(albums);
+        self.scrollbar_state = self.scrollbar_state.content_length(self.albums_table_rows.len());
+
+        Ok(())
+    }
+}
+
+impl<'a> Controller for ArtistAlbumsPanel<'a> {
+    async fn update_model(&mut self) -> anyhow::Result<bool> {
+        let mut result = Ok(false);
+
+        if self.albums_table_state.selected().is_none() && !self.albums_table_rows.is_empty() {
+            self.albums_table_state.select(Some(0));
+            self.scrollbar_state.first();
+            result = Ok(true);
+        }
+
+        result
+    }
+
+    async fn handle_event(&mut self, cmd: Command) -> anyhow::Result<bool> {
+        match cmd {
+            Command::Down => {
+                if let (Some(selected), list_len) = (self.albums_table_state.selected(), self.albums_table_rows.len()) {
+                    // 到达底部时加载下一页
+                    if selected + 1 >= list_len {
+                        self.load_next_page().await?;
+                    }
+
+                    // 直接使用 select_next() 存在越界问题
+                    if selected + 1 < self.albums_table_rows.len() {
+                        self.albums_table_state.select_next();
+                        self.scrollbar_state.next();
+                    }
+                }
+            },
+            Command::Up => {
+                self.albums_table_state.select_previous();
+                self.scrollbar_state.prev();
+            },
+            Command::GoToTop => {
+                self.albums_table_state.select_first();
+                self.scrollbar_state.first();
+            },
+            Command::GoToBottom if !self.albums_table_rows.is_empty() => {
+                // 使用 select_last() 会越界
+                self.albums_table_state.select(Some(self.albums_table_rows.len() - 1));
+                self.scrollbar_state.last();
+            },
+            _ => {},
+        }
+
+        Ok(true)
+    }
+
+    fn update_view(&mut self, _style: &Style) {
+        let mut albums_table = Table::new(self.albums_table_rows.clone(), [Constraint::Min(20), Constraint::Length(10), Constraint::Length(6)])
+            .header(Row::new(vec![Cell::new("专辑"), Cell::new("发行时间"), Cell::new("歌曲数")]).style(TABLE_HEADER_STYLE).height(1))
+            .block({
+                let mut block = Block::default()
+                    .title(Line::from("专辑"))
+                    .title_bottom(Line::from(if self.has_more { "向下滚动到底部加载更多" } else { "没有更多专辑了" }).centered())
+                    .borders(Borders::ALL);
+                if self.focused_status == PanelFocusedStatus::Outside {
+                    block = block.border_style(PANEL_SELECTED_BORDER_STYLE);
+                }
+
+                block
+            });
+
+        // highlight
+        if self.focused_status == PanelFocusedStatus::Inside {
+            albums_table = albums_table.row_highlight_style(ITEM_SELECTED_STYLE).highlight_symbol(">")
+        }
+
+        self.albums_table = albums_table;
+    }
+
+    fn draw(&self, frame: &mut Frame, chunk: Rect) {
+        let mut albums_table_state = self.albums_table_state.clone();
+        frame.render_stateful_widget(&self.albums_table, chunk, &mut albums_table_state);
+
+        // 渲染 scrollbar
+        let scrollbar = Scrollbar::default()
+            .orientation(ScrollbarOrientation::VerticalRight)
+            .track_symbol(None)
+            .begin_symbol(None)
+            .end_symbol(None)
+            .thumb_style(tailwind::ROSE.c800);
+        let scrollbar_area = chunk.inner(Margin { vertical: 1, horizontal: 0 });
+        let mut scrollbar_state = self.scrollbar_state;
+        frame.render_stateful_widget(scrollbar, scrollbar_area, &mut scrollbar_state);
+    }
+}
@@ -0,0 +1,226 @@
+use crate::config::style::*;
+use crate::config::Command;
+use crate::ui::panel::PanelFocusedStatus;
+use crate::ui::Controller;
+use crate::{ncm_client, player};
+use log::error;
+use ncm_api::model::{Comment, CommentSortType};
+use ratatui::layout::Rect;
+use ratatui::prelude::{Line, Span, Style, Text};
+use ratatui::widgets::{Block, Borders, HighlightSpacing, List, ListItem, ListState};
+use ratatui::Frame;
+use unicode_width::UnicodeWidthChar;
+
+pub struct CommentsPanel<'a> {
+    // model
+    pub focused_status: PanelFocusedStatus, // 聚焦状态交给父 screen 管理，面板自身只读不写
+    //
+    song_id: Option<u64>,
+    sort: CommentSortType,
+    comments: Vec<Comment>,
+    total_count: u64,
+    next_cursor: Option<String>,
+    load_error: Option<String>,
+    comments_list_state: ListState,
+
+    // view
+    comments_block: Block<'a>,
+    list_style: Style,
+}
+
+impl<'a> CommentsPanel<'a> {
+    pub fn new(focused_status: PanelFocusedStatus) -> Self {
+        Self {
+            focused_status,
+            song_id: None,
+            sort: CommentSortType::Hot,
+            comments: Vec::new(),
+            total_count: 0,
+            next_cursor: None,
+            load_error: None,
+            comments_list_state: ListState::default(),
+            comments_block: Block::default(),
+            list_style: Style::default(),
+        }
+    }
+}
+
+impl<'a> CommentsPanel<'a> {
+    /// 切换排序方式，并重新获取第一页
+    pub async fn set_sort(&mut self, sort: CommentSortType) {
+        self.sort = sort;
+        self.reload().await;
+    }
+
+    /// 重新获取当前歌曲的第一页评论（如发表评论后）
+    pub async fn reload(&mut self) {
+        self.comments.clear();
+        self.total_count = 0;
+        self.next_cursor = None;
+        self.comments_list_state.select(None);
+
+        self.load_page(None).await;
+    }
+
+    pub fn get_selected_comment(&self) -> Option<Comment> {
+        self.comments_list_state.selected().and_then(|selected| self.comments.get(selected)).cloned()
+    }
+
+    /// 获取一页评论并追加到末尾，失败时记录错误显示在面板中
+    async fn load_page(&mut self, cursor: Option<String>) {
+        let Some(song_id) = self.song_id else {
+            return;
+        };
+
+        let page = ncm_client.lock().await.get_song_comments(song_id, self.sort, cursor.as_deref()).await;
+        match page {
+            Ok(page) => {
+                self.comments.extend(page.comments);
+                self.total_count = page.total_count;
+                self.next_cursor = page.next_cursor;
+                self.load_error = None;
+            },
+            Err(err) => {
+                error!("failed to get comments of song {}: {:?}", song_id, err);
+                self.load_error = Some(err.to_string());
+            },
+        }
+    }
+}
+
+impl<'a> Controller for CommentsPanel<'a> {
+    async fn update_model(&mut self) -> anyhow::Result<bool> {
+        let mut result = Ok(false);
+
+        // 切换到新歌时重新获取评论
+        let song_id = player.lock().await.current_song().as_ref().map(|song| song.id);
+        if song_id != self.song_id {
+            self.song_id = song_id;
+            self.reload().await;
+
+            result = Ok(true);
+        }
+
+        if self.comments_list_state.selected().is_none() && !self.comments.is_empty() {
+            self.comments_list_state.select(Some(0));
+            result = Ok(true);
+        }
+
+        result
+    }
+
+    async fn handle_event(&mut self, cmd: Command) -> anyhow::Result<bool> {
+        match cmd {
+            Command::Down => {
+                if let Some(selected) = self.comments_list_state.selected() {
+                    // 到达末尾时加载下一页
+                    if selected + 1 >= self.comments.len() {
+                        if let Some(cursor) = self.next_cursor.clone() {
+                            self.load_page(Some(cursor)).await;
+                        }
+                    }
+                    // 直接使用 select_next() 存在越界问题
+                    if selected + 1 < self.comments.len() {
+                        self.comments_list_state.select_next();
+                    }
+                }
+            },
+            Command::Up => {
+                self.comments_list_state.select_previous();
+            },
+            Command::GoToTop => {
+                self.comments_list_state.select_first();
+            },
+            Command::GoToBottom if !self.comments.is_empty() => {
+                // 使用 select_last() 会越界
+                self.comments_list_state.select(Some(self.comments.len() - 1));
+            },
+            _ => {},
+        }
+
+        Ok(true)
+    }
+
+    fn update_view(&mut self, style: &Style) {
+        let title = if self.song_id.is_some() {
+            format!("\u{1F4AC}评论（{}，共{}条）", self.sort, self.total_count)
+        } else {
+            String::from("\u{1F4AC}评论")
+        };
+        let bottom_hint = match (&self.load_error, self.song_id) {
+            (Some(err), _) => format!("评论获取失败：{}", err),
+            (None, None) => String::from("播放歌曲后查看评论"),
+            (None, Some(_)) if self.next_cursor.is_some() => String::from("向下滚动加载更多，`c`返回歌词"),
+            (None, Some(_)) => String::from("`c`返回歌词"),
+        };
+
+        let mut block = Block::default().title(Line::from(title).left_aligned()).title_bottom(Line::from(bottom_hint).centered()).borders(Borders::ALL);
+        if self.focused_status == PanelFocusedStatus::Outside {
+            block = block.border_style(PANEL_SELECTED_BORDER_STYLE);
+        }
+
+        self.comments_block = block;
+        self.list_style = *style;
+    }
+
+    fn draw(&self, frame: &mut Frame, chunk: Rect) {
+        // 评论需按面板宽度折行，在绘制时生成
+        let wrap_width = self.comments_block.inner(chunk).width.saturating_sub(2) as usize;
+        let comments_list_items: Vec<ListItem> = self.comments.iter().map(|comment| comment_list_item(comment, wrap_width)).collect();
+
+        let mut comments_list = List::new(comments_list_items).block(self.comments_block.clone()).style(self.list_style);
+
+        // highlight
+        if self.focused_status == PanelFocusedStatus::Inside {
+            comments_list = comments_list.highlight_style(ITEM_SELECTED_STYLE).highlight_symbol(">").highlight_spacing(HighlightSpacing::Always);
+        }
+
+        let mut comments_list_state = self.comments_list_state.clone();
+        frame.render_stateful_widget(comments_list, chunk, &mut comments_list_state);
+    }
+}
+
+/// 一条评论：昵称、点赞数和时间，折行后的内容，被回复的评论，以空行分隔
+fn comment_list_item<'a>(comment: &Comment, wrap_width: usize) -> ListItem<'a> {
+    let mut lines = vec![Line::from(vec![
+        Span::styled(comment.nickname.clone(), COMMENT_NICKNAME_STYLE),
+        Span::raw("  "),
+        Span::styled(format!("\u{2665}{}", comment.liked_count), LIKED_MARK_STYLE),
+        Span::raw("  "),
+        Span::styled(comment.date_time(), COMMENT_META_STYLE),
+    ])];
+
+    for line in wrap_text(&comment.content, wrap_width) {
+        lines.push(Line::from(line));
+    }
+    if let Some((nickname, content)) = comment.replied.as_ref() {
+        for line in wrap_text(&format!("回复 @{}：{}", nickname, content), wrap_width) {
+            lines.push(Line::from(Span::styled(line, COMMENT_META_STYLE)));
+        }
+    }
+    lines.push(Line::from(""));
+
+    ListItem::new(Text::from(lines))
+}
+
+/// 按显示宽度折行（保留原有的换行）
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for raw_line in text.lines() {
+        let mut line = String::new();
+        let mut line_width = 0;
+        for ch in raw_line.chars() {
+            let ch_width = ch.width().unwrap_or(0);
+            if width > 0 && line_width + ch_width > width {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0;
+            }
+            line.push(ch);
+            line_width += ch_width;
+        }
+        lines.push(line);
+    }
+
+    lines
+}
@@ -0,0 +1,91 @@
+use crate::config::Command;
+use crate::ui::widget::{CoverArt, GraphicsProtocol};
+use crate::ui::Controller;
+use crate::{ncm_client, player};
+use image::RgbImage;
+use log::error;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Line, Style};
+use ratatui::widgets::{Block, Borders};
+use ratatui::Frame;
+use tokio::task::{self, JoinHandle};
+
+pub struct CoverPanel {
+    // model
+    protocol: GraphicsProtocol,
+    cover_url: Option<String>,
+    cover: Option<CoverArt>,
+    loading_cover: Option<JoinHandle<anyhow::Result<RgbImage>>>, // 后台下载、解码中的封面
+
+    // view
+    block: Block<'static>,
+}
+
+impl CoverPanel {
+    pub fn new() -> Self {
+        Self {
+            protocol: GraphicsProtocol::detect(),
+            cover_url: None,
+            cover: None,
+            loading_cover: None,
+            block: Block::default(),
+        }
+    }
+
+    /// 是否有封面可以显示
+    pub fn has_cover(&self) -> bool {
+        self.cover.is_some()
+    }
+}
+
+impl Controller for CoverPanel {
+    async fn update_model(&mut self) -> anyhow::Result<bool> {
+        let cover_url = player.lock().await.current_song().as_ref().and_then(|song| song.cover_url.clone());
+        if cover_url != self.cover_url {
+            // 切换到新歌，在后台获取封面，不阻塞界面
+            if let Some(loading_cover) = self.loading_cover.take() {
+                loading_cover.abort();
+            }
+            self.cover = None;
+            if let Some(url) = cover_url.clone() {
+                let get_cover_image = ncm_client.lock().await.get_cover_image(&url);
+                self.loading_cover = Some(task::spawn(async move {
+                    let bytes = get_cover_image.await?;
+                    Ok(image::load_from_memory(&bytes)?.to_rgb8())
+                }));
+            }
+            self.cover_url = cover_url;
+
+            return Ok(true);
+        }
+
+        // 封面获取完成后显示，失败时不显示封面
+        if self.loading_cover.as_ref().is_some_and(|loading_cover| loading_cover.is_finished()) {
+            if let Some(loading_cover) = self.loading_cover.take() {
+                match loading_cover.await.map_err(anyhow::Error::from).and_then(|result| result) {
+                    Ok(image) => self.cover = Some(CoverArt::new(image, self.protocol)),
+                    Err(err) => error!("failed to load cover {:?}: {:?}", self.cover_url, err),
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    async fn handle_event(&mut self, _cmd: Command) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
+    fn update_view(&mut self, style: &Style) {
+        self.block = Block::default().title(Line::from("专辑封面").centered()).borders(Borders::ALL).style(*style);
+    }
+
+    fn draw(&self, frame: &mut Frame, chunk: Rect) {
+        if let Some(cover) = self.cover.as_ref() {
+            frame.render_widget(&self.block, chunk);
+            frame.render_widget(cover, self.block.inner(chunk));
+        }
+    }
+}
@@ -3,9 +3,9 @@ use crate::config::Command;
 use crate::player;
 use crate::ui::panel::PanelFocusedStatus;
 use crate::ui::Controller;
-use ncm_api::model::Song;
+use ncm_api::model::{LyricLine, LyricWord, Lyrics, Song};
 use ratatui::layout::Rect;
-use ratatui::prelude::{Line, Style, Text};
+use ratatui::prelude::{Line, Span, Style, Text};
 use ratatui::widgets::{Block, Borders, HighlightSpacing, List, ListItem, ListState};
 use ratatui::Frame;
 
@@ -14,8 +14,10 @@ pub struct LyricPanel<'a> {
     pub focused_status: PanelFocusedStatus, // 聚焦状态交给父 screen 管理，面板自身只读不写
     //
     song: Option<Song>,
+    lyrics: Lyrics,
     song_lyric_list_items: Vec<ListItem<'a>>,
     song_lyric_list_state: ListState,
+    karaoke: Option<(usize, usize)>, // 以逐字高亮显示的歌词行 (行号, 已唱到的字符数)
 
     // view
     song_lyric_list: List<'a>,
@@ -31,8 +33,10 @@ impl<'a> LyricPanel<'a> {
         Self {
             focused_status,
             song: None,
+            lyrics: Lyrics::new(),
             song_lyric_list_items,
             song_lyric_list_state: ListState::default(),
+            karaoke: None,
             song_lyric_list: List::default(),
         }
     }
@@ -53,30 +57,25 @@ impl<'a> Controller for LyricPanel<'a> {
 
                     result = Ok(true);
                 }
+
+                // 逐字歌词随播放进度高亮
+                let position = player_guard.position().map(|position| position.mseconds());
+                if self.update_karaoke(position) {
+                    result = Ok(true);
+                }
             }
         } else {
             // 切换到新歌
             self.song = player_guard.current_song().clone();
             // 更新歌词 ListItem
+            self.karaoke = None;
             if let Some(lyrics) = player_guard.current_song_lyrics() {
                 // 有歌词
-                self.song_lyric_list_items = lyrics
-                    .iter()
-                    .map(|lyric_line| {
-                        let mut lines: Vec<Line> = Vec::new();
-                        lines.push(Line::from(lyric_line.lyric_line.to_owned()).centered());
-                        if let Some(trans_lyric_line) = lyric_line.trans_lyric_line.as_ref() {
-                            lines.push(Line::from(trans_lyric_line.to_owned()).centered());
-                        }
-                        // TODO: 添加罗马音显示设置
-                        // if let Some(roman_lyric_line) = lyric_line.roman_lyric_line.as_ref() {
-                        //     lines.push(Line::from(roman_lyric_line.to_owned()).centered());
-                        // }
-                        ListItem::new(Text::from(lines))
-                    })
-                    .collect();
+                self.song_lyric_list_items = lyrics.iter().map(|lyric_line| lyric_list_item(lyric_line, None)).collect();
+                self.lyrics = lyrics;
             } else {
                 // 无歌词（纯音乐或网络异常）
+                self.lyrics = Lyrics::new();
                 self.song_lyric_list_items = Vec::new();
                 self.song_lyric_list_items.push(ListItem::new(Text::from(Line::from("无歌词，请欣赏").centered())));
             }
@@ -134,7 +133,7 @@ impl<'a> Controller for LyricPanel<'a> {
             Some(song) => song_lyric_list.block({
                 let mut block = Block::default()
                     .title(Line::from(format!("\u{1F3B5}{}", song.name)).left_aligned())
-                    .title(Line::from(format!("\u{1F3A4}{}", song.singer)).right_aligned())
+                    .title(Line::from(format!("\u{1F3A4}{}", song.artists_name())).right_aligned())
                     .title_bottom(Line::from(format!("\u{1F4DA}{}", song.album)).centered())
                     .borders(Borders::ALL);
                 if self.focused_status == PanelFocusedStatus::Outside {
@@ -156,6 +155,8 @@ impl<'a> Controller for LyricPanel<'a> {
         // highlight
         song_lyric_list = if self.focused_status == PanelFocusedStatus::Inside {
             song_lyric_list.highlight_style(ITEM_SELECTED_STYLE)
+        } else if self.karaoke.is_some() {
+            song_lyric_list.highlight_style(LYRIC_KARAOKE_FOCUSED_STYLE).highlight_spacing(HighlightSpacing::WhenSelected)
         } else {
             song_lyric_list.highlight_style(LYRIC_FOCUSED_STYLE).highlight_spacing(HighlightSpacing::WhenSelected)
         };
@@ -174,6 +175,32 @@ impl<'a> Controller for LyricPanel<'a> {
 }
 
 impl<'a> LyricPanel<'a> {
+    /// 按播放进度更新当前歌词行的逐字高亮，返回是否需要重绘
+    fn update_karaoke(&mut self, position: Option<u64>) -> bool {
+        let karaoke = match (self.song_lyric_list_state.selected(), position) {
+            (Some(index), Some(position)) => self.lyrics.get(index).and_then(|lyric_line| lyric_line.words.as_ref()).map(|words| (index, sung_chars_count(words, position))),
+            _ => None,
+        };
+        if karaoke == self.karaoke {
+            return false;
+        }
+
+        // 恢复上一行的普通显示
+        if let Some((index, _)) = self.karaoke {
+            if let (Some(lyric_line), Some(item)) = (self.lyrics.get(index), self.song_lyric_list_items.get_mut(index)) {
+                *item = lyric_list_item(lyric_line, None);
+            }
+        }
+        if let Some((index, sung_chars)) = karaoke {
+            if let (Some(lyric_line), Some(item)) = (self.lyrics.get(index), self.song_lyric_list_items.get_mut(index)) {
+                *item = lyric_list_item(lyric_line, Some(sung_chars));
+            }
+        }
+        self.karaoke = karaoke;
+
+        true
+    }
+
     #[inline]
     /// 修正 offset 以使歌词居中
     fn correct_offset_to_make_lyric_centered(&self, lyric_list_state: &mut ListState, available_line_count: usize) {
@@ -201,3 +228,47 @@ impl<'a> LyricPanel<'a> {
         }
     }
 }
+
+/// 一句歌词（带翻译时占多行），`sung_chars` 不为 None 时按逐字歌词显示，已唱到的字高亮
+fn lyric_list_item<'a>(lyric_line: &LyricLine, sung_chars: Option<usize>) -> ListItem<'a> {
+    let mut lines: Vec<Line> = Vec::new();
+    match (lyric_line.words.as_ref(), sung_chars) {
+        (Some(words), Some(sung_chars)) => {
+            let text: String = words.iter().map(|(_, _, text)| text.as_str()).collect();
+            let split_at = text.char_indices().nth(sung_chars).map_or(text.len(), |(i, _)| i);
+            lines.push(Line::from(vec![Span::styled(text[..split_at].to_string(), LYRIC_FOCUSED_STYLE), Span::styled(text[split_at..].to_string(), LYRIC_UNSUNG_STYLE)]).centered());
+            if let Some(trans_lyric_line) = lyric_line.trans_lyric_line.as_ref() {
+                lines.push(Line::styled(trans_lyric_line.to_owned(), LYRIC_FOCUSED_STYLE).centered());
+            }
+        },
+        _ => {
+            lines.push(Line::from(lyric_line.lyric_line.to_owned()).centered());
+            if let Some(trans_lyric_line) = lyric_line.trans_lyric_line.as_ref() {
+                lines.push(Line::from(trans_lyric_line.to_owned()).centered());
+            }
+        },
+    }
+    // TODO: 添加罗马音显示设置
+    // if let Some(roman_lyric_line) = lyric_line.roman_lyric_line.as_ref() {
+    //     lines.push(Line::from(roman_lyric_line.to_owned()).centered());
+    // }
+
+    ListItem::new(Text::from(lines))
+}
+
+/// 播放到 `position`（ms）时一行逐字歌词中已唱到的字符数，正在唱的字按进度计入部分字符
+fn sung_chars_count(words: &[LyricWord], position: u64) -> usize {
+    words
+        .iter()
+        .map(|(start, duration, text)| {
+            let chars = text.chars().count();
+            if position >= start + duration {
+                chars
+            } else if position >= *start {
+                (chars as u64 * (position - start) / (*duration).max(1)) as usize
+            } else {
+                0
+            }
+        })
+        .sum()
+}
@@ -2,19 +2,24 @@ use crate::config::style::*;
 use crate::config::Command;
 use crate::ui::panel::PanelFocusedStatus;
 use crate::ui::Controller;
-use crate::{ncm_client, player};
+use crate::{actions, ncm_client, player};
 use ncm_api::model::Song;
 use ratatui::layout::{Constraint, Rect};
 use ratatui::prelude::{Margin, Style};
 use ratatui::style::palette::tailwind;
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Cell, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table, TableState};
 use ratatui::Frame;
+use std::collections::HashSet;
 
 pub struct PlaylistPanel<'a> {
     // model
     pub focused_status: PanelFocusedStatus, // 聚焦状态交给父 screen 管理，面板自身只读不写
     //
     playlist_name: String,
+    playlist: Vec<Song>,
+    liked_song_ids: HashSet<u64>,
+    liked_song_ids_version: usize,
     playlist_table_rows: Vec<Row<'a>>,
     playlist_table_state: TableState,
     scrollbar_state: ScrollbarState,
@@ -28,6 +33,9 @@ impl<'a> PlaylistPanel<'a> {
         Self {
             focused_status,
             playlist_name: String::new(),
+            playlist: Vec::new(),
+            liked_song_ids: HashSet::new(),
+            liked_song_ids_version: 0,
             playlist_table_rows: Vec::new(),
             playlist_table_state: TableState::new(),
             scrollbar_state: ScrollbarState::new(0),
@@ -42,36 +50,89 @@ impl<'a> PlaylistPanel<'a> {
         let player_guard = player.lock().await;
         let current_playlist_name = player_guard.current_playlist_name();
 
-        if self.playlist_name != *current_playlist_name {
-            let current_playlist = player_guard.current_playlist();
+        let current_playlist = player_guard.current_playlist();
 
+        if self.playlist_name != *current_playlist_name {
             self.set_model(current_playlist_name, current_playlist);
+        } else if self.playlist != *current_playlist {
+            // 同一播放列表内容变化（歌单编辑、重新进入私人FM）时保留 selected
+            self.playlist = current_playlist.clone();
+            drop(player_guard);
+
+            self.update_playlist_table_rows();
+            self.scrollbar_state = self.scrollbar_state.content_length(self.playlist_table_rows.len());
+            if let Some(selected) = self.playlist_table_state.selected() {
+                if selected >= self.playlist_table_rows.len() {
+                    self.playlist_table_state.select(self.playlist_table_rows.len().checked_sub(1));
+                }
+            }
         }
 
         Ok(())
     }
 
+    /// 当前播放列表在末尾追加了歌曲（私人FM 预取下一批）时，同步追加显示（不改变 selected）
+    pub async fn sync_current_playlist_growth(&mut self) -> anyhow::Result<bool> {
+        let player_guard = player.lock().await;
+        let current_playlist = player_guard.current_playlist();
+
+        if self.playlist_name == *player_guard.current_playlist_name() && current_playlist.len() > self.playlist.len() && current_playlist.starts_with(&self.playlist) {
+            self.playlist = current_playlist.clone();
+            drop(player_guard);
+
+            self.update_playlist_table_rows();
+            self.scrollbar_state = self.scrollbar_state.content_length(self.playlist_table_rows.len());
+
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
     /// 手动设置 model
     ///
     /// 在 main_screen 由 self.update_model_by_current_playlist() 调用，在 playlist_screen 由外部直接调用
     pub fn set_model(&mut self, playlist_name: &String, playlist: &Vec<Song>) {
         self.playlist_name = playlist_name.clone();
-        self.playlist_table_rows = playlist
+        self.playlist = playlist.clone();
+        self.update_playlist_table_rows();
+
+        // 更新 playlist_table 的 selected，防止悬空
+        self.playlist_table_state.select(None);
+
+        self.scrollbar_state = ScrollbarState::new(self.playlist_table_rows.len());
+    }
+
+    pub fn get_selected_song(&self) -> Option<Song> {
+        self.playlist_table_state.selected().and_then(|selected| self.playlist.get(selected)).cloned()
+    }
+
+    /// 根据 playlist 和喜欢的歌曲更新表格行
+    fn update_playlist_table_rows(&mut self) {
+        self.playlist_table_rows = self
+            .playlist
             .iter()
             .map(|song| {
                 Row::from_iter(vec![
-                    Cell::new(song.name.clone()),
-                    Cell::new(song.singer.clone()),
+                    // 心动模式推荐的歌曲（不在用户歌单中）额外标记
+                    Cell::new(match (self.liked_song_ids.contains(&song.id), song.is_recommended) {
+                        (true, true) => "\u{2665}\u{2726}",
+                        (true, false) => "\u{2665}",
+                        (false, true) => "\u{2726}",
+                        (false, false) => "",
+                    })
+                    .style(LIKED_MARK_STYLE),
+                    // 每日推荐歌曲在歌名后显示推荐理由
+                    Cell::new(match &song.recommend_reason {
+                        Some(reason) => Line::from(vec![Span::from(song.name.clone()), Span::from(format!("  {}", reason)).style(RECOMMEND_REASON_STYLE)]),
+                        None => Line::from(song.name.clone()),
+                    }),
+                    Cell::new(song.artists_name()),
                     Cell::new(song.album.clone()),
-                    Cell::new(format!("{:02}:{:02}", song.duration.clone() / 60000, song.duration.clone() % 60000 / 1000)),
+                    Cell::new(format!("{:02}:{:02}", song.duration / 60000, song.duration % 60000 / 1000)),
                 ])
             })
             .collect();
-
-        // 更新 playlist_table 的 selected，防止悬空
-        self.playlist_table_state.select(None);
-
-        self.scrollbar_state = ScrollbarState::new(self.playlist_table_rows.len());
     }
 }
 
@@ -79,6 +140,19 @@ impl<'a> Controller for PlaylistPanel<'a> {
     async fn update_model(&mut self) -> anyhow::Result<bool> {
         let mut result = Ok(false);
 
+        // 喜欢的歌曲变化时更新标记（不改变 selected），只比较版本，变化时才复制
+        let ncm_client_guard = ncm_client.lock().await;
+        if self.liked_song_ids_version != ncm_client_guard.liked_song_ids_version() {
+            self.liked_song_ids_version = ncm_client_guard.liked_song_ids_version();
+            self.liked_song_ids = ncm_client_guard.cached_liked_song_ids().clone();
+            drop(ncm_client_guard);
+
+            self.update_playlist_table_rows();
+            result = Ok(true);
+        } else {
+            drop(ncm_client_guard);
+        }
+
         if self.playlist_table_state.selected() == None && !self.playlist_table_rows.is_empty() {
             self.playlist_table_state.select(Some(0));
             self.scrollbar_state.first();
@@ -107,7 +181,7 @@ impl<'a> Controller for PlaylistPanel<'a> {
                 player
                     .lock()
                     .await
-                    .play_particularly_now(self.playlist_table_state.selected().unwrap_or(0), ncm_client.lock().await)
+                    .play_particularly_now(self.playlist_table_state.selected().unwrap_or(0), &*ncm_client.lock().await)
                     .await?;
             },
             Command::WhereIsThisSong => {
@@ -144,6 +218,16 @@ impl<'a> Controller for PlaylistPanel<'a> {
             Command::RefreshPlaylist => {
                 self.update_model_by_current_playlist().await?;
             },
+            Command::Like(like) => {
+                if let Some(song) = self.playlist_table_state.selected().and_then(|selected| self.playlist.get(selected)) {
+                    actions::like_song(song.id, Some(like)).await?;
+                }
+            },
+            Command::ToggleLike => {
+                if let Some(song) = self.playlist_table_state.selected().and_then(|selected| self.playlist.get(selected)) {
+                    actions::like_song(song.id, None).await?;
+                }
+            },
             _ => {},
         }
 
@@ -153,20 +237,23 @@ impl<'a> Controller for PlaylistPanel<'a> {
     fn update_view(&mut self, _style: &Style) {
         let header_style = Style::default().fg(tailwind::WHITE).bg(tailwind::RED.c300);
 
-        let mut playlist_table = Table::new(self.playlist_table_rows.clone(), [Constraint::Min(40), Constraint::Min(15), Constraint::Min(15), Constraint::Length(6)])
-            .header(
-                Row::new(vec![Cell::new("曲名"), Cell::new("歌手/乐手"), Cell::new("专辑"), Cell::new("时长")])
-                    .style(header_style)
-                    .height(1),
-            )
-            .block({
-                let mut block = Block::default().title(format!("Playlist: {}\u{1F4DC}", self.playlist_name.clone())).borders(Borders::ALL);
-                if self.focused_status == PanelFocusedStatus::Outside {
-                    block = block.border_style(PANEL_SELECTED_BORDER_STYLE);
-                }
+        let mut playlist_table = Table::new(
+            self.playlist_table_rows.clone(),
+            [Constraint::Length(2), Constraint::Min(40), Constraint::Min(15), Constraint::Min(15), Constraint::Length(6)],
+        )
+        .header(
+            Row::new(vec![Cell::new(""), Cell::new("曲名"), Cell::new("歌手/乐手"), Cell::new("专辑"), Cell::new("时长")])
+                .style(header_style)
+                .height(1),
+        )
+        .block({
+            let mut block = Block::default().title(format!("Playlist: {}\u{1F4DC}", self.playlist_name.clone())).borders(Borders::ALL);
+            if self.focused_status == PanelFocusedStatus::Outside {
+                block = block.border_style(PANEL_SELECTED_BORDER_STYLE);
+            }
 
-                block
-            });
+            block
+        });
 
         // highlight
         if self.focused_status == PanelFocusedStatus::Inside {
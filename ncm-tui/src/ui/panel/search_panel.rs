@@ -0,0 +1,250 @@
+use crate::config::style::*;
+use crate::config::Command;
+use crate::ncm_client;
+use crate::ui::panel::PanelFocusedStatus;
+use crate::ui::Controller;
+use ncm_api::model::{SearchResult, SearchType};
+use ratatui::layout::{Constraint, Margin, Rect};
+use ratatui::prelude::Style;
+use ratatui::style::palette::tailwind;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Cell, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table, TableState};
+use ratatui::Frame;
+
+/// 每页搜索结果数量
+const SEARCH_PAGE_SIZE: usize = 30;
+
+pub struct SearchPanel<'a> {
+    // model
+    pub focused_status: PanelFocusedStatus, // 聚焦状态交给父 screen 管理，面板自身只读不写
+    //
+    keywords: String,
+    search_type: SearchType,
+    search_result: SearchResult,
+    has_more: bool, // 是否还有下一页
+    search_result_table_rows: Vec<Row<'a>>,
+    search_result_table_state: TableState,
+    scrollbar_state: ScrollbarState,
+
+    // view
+    search_result_table: Table<'a>,
+}
+
+impl<'a> SearchPanel<'a> {
+    pub fn new(focused_status: PanelFocusedStatus) -> Self {
+        Self {
+            focused_status,
+            keywords: String::new(),
+            search_type: SearchType::Song,
+            search_result: SearchResult::Songs(Vec::new()),
+            has_more: false,
+            search_result_table_rows: Vec::new(),
+            search_result_table_state: TableState::new(),
+            scrollbar_state: ScrollbarState::new(0),
+            search_result_table: Table::default(),
+        }
+    }
+}
+
+impl<'a> SearchPanel<'a> {
+    /// 发起新的搜索（获取第一页）
+    pub async fn search(&mut self, keywords: &[String]) -> anyhow::Result<()> {
+        self.keywords = keywords.join(" ");
+        self.research().await
+    }
+
+    /// 切换到下一个搜索类型，并以当前关键词重新搜索
+    pub async fn switch_search_type(&mut self) -> anyhow::Result<()> {
+        self.search_type = self.search_type.next();
+        self.research().await
+    }
+
+    pub fn keywords(&self) -> &String {
+        &self.keywords
+    }
+
+    pub fn search_type(&self) -> SearchType {
+        self.search_type
+    }
+
+    pub fn search_result(&self) -> &SearchResult {
+        &self.search_result
+    }
+
+    pub fn get_selected_index(&self) -> Option<usize> {
+        self.search_result_table_state.selected()
+    }
+
+    /// 以当前关键词和搜索类型重新搜索
+    async fn research(&mut self) -> anyhow::Result<()> {
+        self.search_result = SearchResult::Songs(Vec::new());
+        self.search_result_table_rows = Vec::new();
+        self.has_more = true;
+
+        self.load_next_page().await?;
+
+        // 防止悬空
+        self.search_result_table_state.select(None);
+        self.scrollbar_state = ScrollbarState::new(self.search_result_table_rows.len());
+
+        Ok(())
+    }
+
+    /// 获取下一页搜索结果，追加到已有结果之后
+    async fn load_next_page(&mut self) -> anyhow::Result<()> {
+        if !self.has_more || self.keywords.is_empty() {
+            return Ok(());
+        }
+
+        let offset = if self.search_result.search_type() == self.search_type { self.search_result.len() } else { 0 };
+        let search_result = ncm_client.lock().await.search(&self.keywords, self.search_type, offset, SEARCH_PAGE_SIZE).await?;
+
+        self.has_more = search_result.len() == SEARCH_PAGE_SIZE;
+        self.search_result.append(search_result);
+        self.search_result_table_rows = match &self.search_result {
+            SearchResult::Songs(songs) => songs
+                .iter()
+                .map(|song| {
+                    Row::from_iter(vec![
+                        Cell::new(song.name.clone()),
+                        Cell::new(song.artists_name()),
+                        Cell::new(song.album.clone()),
+                        Cell::new(format!("{:02}:{:02}", song.duration / 60000, song.duration % 60000 / 1000)),
+                    ])
+                })
+                .collect(),
+            SearchResult::Albums(albums) => albums
+                .iter()
+                .map(|album| Row::from_iter(vec![Cell::new(album.name.clone()), Cell::new(album.artist.clone()), Cell::new(format!("{:>6}", album.songs_count))]))
+                .collect(),
+            SearchResult::Artists(artists) => artists
+                .iter()
+                .map(|artist| {
+                    Row::from_iter(vec![
+                        Cell::new(artist.name.clone()),
+                        Cell::new(format!("{:>6}", artist.albums_count)),
+                        Cell::new(format!("{:>6}", artist.songs_count)),
+                    ])
+                })
+                .collect(),
+            SearchResult::Songlists(songlists) => songlists
+                .iter()
+                .map(|songlist| {
+                    Row::from_iter(vec![
+                        Cell::new(songlist.name.clone()),
+                        Cell::new(songlist.creator.clone()),
+                        Cell::new(format!("{:>6}", songlist.songs_count)),
+                    ])
+                })
+                .collect(),
+            SearchResult::Lyrics(lyrics) => lyrics
+                .iter()
+                .map(|(song, matched_lyric)| Row::from_iter(vec![Cell::new(song.name.clone()), Cell::new(song.artists_name()), Cell::new(matched_lyric.clone())]))
+                .collect(),
+        };
+        self.scrollbar_state = self.scrollbar_state.content_length(self.search_result_table_rows.len());
+
+        Ok(())
+    }
+}
+
+impl<'a> Controller for SearchPanel<'a> {
+    async fn update_model(&mut self) -> anyhow::Result<bool> {
+        let mut result = Ok(false);
+
+        if self.search_result_table_state.selected().is_none() && !self.search_result_table_rows.is_empty() {
+            self.search_result_table_state.select(Some(0));
+            self.scrollbar_state.first();
+            result = Ok(true);
+        }
+
+        result
+    }
+
+    async fn handle_event(&mut self, cmd: Command) -> anyhow::Result<bool> {
+        match cmd {
+            Command::Down => {
+                if let (Some(selected), list_len) = (self.search_result_table_state.selected(), self.search_result_table_rows.len()) {
+                    // 到达底部时加载下一页
+                    if selected + 1 >= list_len {
+                        self.load_next_page().await?;
+                    }
+
+                    // 直接使用 select_next() 存在越界问题
+                    if selected + 1 < self.search_result_table_rows.len() {
+                        self.search_result_table_state.select_next();
+                        self.scrollbar_state.next();
+                    }
+                }
+            },
+            Command::Up => {
+                self.search_result_table_state.select_previous();
+                self.scrollbar_state.prev();
+            },
+            Command::GoToTop => {
+                self.search_result_table_state.select_first();
+                self.scrollbar_state.first();
+            },
+            Command::GoToBottom if !self.search_result_table_rows.is_empty() => {
+                // 使用 select_last() 会越界
+                self.search_result_table_state.select(Some(self.search_result_table_rows.len() - 1));
+                self.scrollbar_state.last();
+            },
+            _ => {},
+        }
+
+        Ok(true)
+    }
+
+    fn update_view(&mut self, _style: &Style) {
+        // 不同搜索类型的表头和列宽
+        let (header_cells, widths) = match self.search_type {
+            SearchType::Song => (
+                vec!["曲名", "歌手/乐手", "专辑", "时长"],
+                vec![Constraint::Min(40), Constraint::Min(15), Constraint::Min(15), Constraint::Length(6)],
+            ),
+            SearchType::Album => (vec!["专辑", "歌手/乐手", "歌曲数"], vec![Constraint::Min(30), Constraint::Min(15), Constraint::Length(6)]),
+            SearchType::Artist => (vec!["歌手/乐手", "专辑数", "歌曲数"], vec![Constraint::Min(30), Constraint::Length(6), Constraint::Length(6)]),
+            SearchType::Songlist => (vec!["歌单", "创建者", "歌曲数"], vec![Constraint::Min(30), Constraint::Min(10), Constraint::Length(6)]),
+            SearchType::Lyric => (vec!["曲名", "歌手/乐手", "歌词"], vec![Constraint::Min(20), Constraint::Min(15), Constraint::Min(40)]),
+        };
+
+        let mut search_result_table = Table::new(self.search_result_table_rows.clone(), widths)
+            .header(Row::new(header_cells.into_iter().map(Cell::new)).style(TABLE_HEADER_STYLE).height(1))
+            .block({
+                let mut block = Block::default()
+                    .title(Line::from(format!("Search[{}]: {}\u{1F50D}", self.search_type, self.keywords)))
+                    .title(Line::from("按`t`切换搜索类型").right_aligned())
+                    .title_bottom(Line::from(if self.has_more { "向下滚动到底部加载更多" } else { "没有更多结果了" }).centered())
+                    .borders(Borders::ALL);
+                if self.focused_status == PanelFocusedStatus::Outside {
+                    block = block.border_style(PANEL_SELECTED_BORDER_STYLE);
+                }
+
+                block
+            });
+
+        // highlight
+        if self.focused_status == PanelFocusedStatus::Inside {
+            search_result_table = search_result_table.row_highlight_style(ITEM_SELECTED_STYLE).highlight_symbol(">")
+        }
+
+        self.search_result_table = search_result_table;
+    }
+
+    fn draw(&self, frame: &mut Frame, chunk: Rect) {
+        let mut search_result_table_state = self.search_result_table_state.clone();
+        frame.render_stateful_widget(&self.search_result_table, chunk, &mut search_result_table_state);
+
+        // 渲染 scrollbar
+        let scrollbar = Scrollbar::default()
+            .orientation(ScrollbarOrientation::VerticalRight)
+            .track_symbol(None)
+            .begin_symbol(None)
+            .end_symbol(None)
+            .thumb_style(tailwind::ROSE.c800);
+        let scrollbar_area = chunk.inner(Margin { vertical: 1, horizontal: 0 });
+        let mut scrollbar_state = self.scrollbar_state;
+        frame.render_stateful_widget(scrollbar, scrollbar_area, &mut scrollbar_state);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::config::style::*;
+use crate::config::Command;
+use crate::ui::panel::PanelFocusedStatus;
+use crate::ui::Controller;
+use ncm_api::model::Artist;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::prelude::Style;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Cell, Row, Table, TableState};
+use ratatui::Frame;
+
+pub struct SimilarArtistsPanel<'a> {
+    // model
+    pub focused_status: PanelFocusedStatus, // 聚焦状态交给父 screen 管理，面板自身只读不写
+    //
+    artists: Vec<Artist>,
+    artists_table_rows: Vec<Row<'a>>,
+    artists_table_state: TableState,
+
+    // view
+    artists_table: Table<'a>,
+}
+
+impl<'a> SimilarArtistsPanel<'a> {
+    pub fn new(focused_status: PanelFocusedStatus) -> Self {
+        Self {
+            focused_status,
+            artists: Vec::new(),
+            artists_table_rows: Vec::new(),
+            artists_table_state: TableState::new(),
+            artists_table: Table::default(),
+        }
+    }
+}
+
+impl<'a> SimilarArtistsPanel<'a> {
+    pub fn set_model(&mut self, artists: Vec<Artist>) {
+        self.artists_table_rows = artists.iter().map(|artist| Row::from_iter(vec![Cell::new(artist.name.clone())])).collect();
+        self.artists = artists;
+
+        // 防止悬空
+        self.artists_table_state.select(None);
+    }
+
+    pub fn get_selected_artist(&self) -> Option<Artist> {
+        self.artists_table_state.selected().and_then(|selected| self.artists.get(selected)).cloned()
+    }
+}
+
+impl<'a> Controller for SimilarArtistsPanel<'a> {
+    async fn update_model(&mut self) -> anyhow::Result<bool> {
+        let mut result = Ok(false);
+
+        if self.artists_table_state.selected().is_none() && !self.artists_table_rows.is_empty() {
+            self.artists_table_state.select(Some(0));
+            result = Ok(true);
+        }
+
+        result
+    }
+
+    async fn handle_event(&mut self, cmd: Command) -> anyhow::Result<bool> {
+        match cmd {
+            Command::Down => {
+                // 直接使用 select_next() 存在越界问题
+                if let (Some(selected), list_len) = (self.artists_table_state.selected(), self.artists_table_rows.len()) {
+                    if selected + 1 < list_len {
+                        self.artists_table_state.select_next();
+                    }
+                }
+            },
+            Command::Up => {
+                self.artists_table_state.select_previous();
+            },
+            Command::GoToTop => {
+                self.artists_table_state.select_first();
+            },
+            Command::GoToBottom if !self.artists_table_rows.is_empty() => {
+                // 使用 select_last() 会越界
+                self.artists_table_state.select(Some(self.artists_table_rows.len() - 1));
+            },
+            _ => {},
+        }
+
+        Ok(true)
+    }
+
+    fn update_view(&mut self, _style: &Style) {
+        let mut artists_table = Table::new(self.artists_table_rows.clone(), [Constraint::Min(10)])
+            .header(Row::new(vec![Cell::new("歌手/乐手")]).style(TABLE_HEADER_STYLE).height(1))
+            .block({
+                let mut block = Block::default()
+                    .title(Line::from("相似歌手"))
+                    .title_bottom(Line::from(if self.artists.is_empty() { "登录后可查看相似歌手" } else { "" }).centered())
+                    .borders(Borders::ALL);
+                if self.focused_status == PanelFocusedStatus::Outside {
+                    block = block.border_style(PANEL_SELECTED_BORDER_STYLE);
+                }
+
+                block
+            });
+
+        // highlight
+        if self.focused_status == PanelFocusedStatus::Inside {
+            artists_table = artists_table.row_highlight_style(ITEM_SELECTED_STYLE).highlight_symbol(">")
+        }
+
+        self.artists_table = artists_table;
+    }
+
+    fn draw(&self, frame: &mut Frame, chunk: Rect) {
+        let mut artists_table_state = self.artists_table_state.clone();
+        frame.render_stateful_widget(&self.artists_table, chunk, &mut artists_table_state);
+    }
+}
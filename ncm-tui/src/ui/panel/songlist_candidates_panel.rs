@@ -7,7 +7,7 @@ use ncm_api::model::Songlist;
 use ratatui::layout::{Margin, Rect};
 use ratatui::prelude::{Constraint, Style};
 use ratatui::style::palette::tailwind;
-use ratatui::text::Line;
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Cell, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table, TableState};
 use ratatui::Frame;
 
@@ -17,6 +17,9 @@ pub struct SonglistsPanel<'a> {
     //
     username: String,
     songlists: Vec<Songlist>,
+    songlists_version: usize, // 与 player 中的歌单版本不同时重新同步
+    created_count: usize,
+    subscribed_count: usize,
     songlists_table_rows: Vec<Row<'a>>,
     songlists_table_state: TableState,
     scrollbar_state: ScrollbarState,
@@ -31,6 +34,9 @@ impl<'a> SonglistsPanel<'a> {
             focused_status,
             username: String::new(),
             songlists: Vec::new(),
+            songlists_version: 0,
+            created_count: 0,
+            subscribed_count: 0,
             songlists_table_rows: Vec::new(),
             songlists_table_state: TableState::new(),
             scrollbar_state: ScrollbarState::new(0),
@@ -59,33 +65,63 @@ impl<'a> Controller for SonglistsPanel<'a> {
     async fn update_model(&mut self) -> anyhow::Result<bool> {
         let mut result = Ok(false);
 
-        if self.songlists_table_rows.is_empty() {
-            let player_guard = player.lock().await;
-            let user_all_songlists = player_guard.songlists();
+        // 歌单在后台分页加载或被编辑时重新同步（不改变 selected）
+        let player_guard = player.lock().await;
+        let user_all_songlists = player_guard.songlists();
+        if self.songlists_version != player_guard.songlists_version() {
+            let is_first_load = self.songlists_table_rows.is_empty();
+            self.songlists_version = player_guard.songlists_version();
 
             if let Some(login_account) = ncm_client.lock().await.login_account() {
                 self.username = login_account.nickname;
             }
             self.songlists = user_all_songlists.clone();
+            self.subscribed_count = user_all_songlists.iter().filter(|songlist| songlist.subscribed).count();
+            self.created_count = user_all_songlists.iter().filter(|songlist| !songlist.subscribed && !songlist.is_pseudo()).count();
             self.songlists_table_rows = user_all_songlists
                 .iter()
                 .map(|songlist| {
-                    Row::from_iter(vec![
-                        Cell::new(songlist.name.clone()),
-                        Cell::new(songlist.creator.clone()),
-                        Cell::new(format!("{:>6}", songlist.songs_count)),
-                    ])
+                    if songlist.is_pseudo() {
+                        // 伪歌单置顶，歌曲数在装载前未知
+                        let mark = if songlist.is_cloud_drive() { "\u{2601}" } else { "\u{2605}" };
+                        Row::from_iter(vec![Cell::new(format!("{} {}", mark, songlist.name)), Cell::new(songlist.creator.clone()), Cell::new("")])
+                    } else {
+                        // 收藏的歌单加上标记，与创建的歌单区分
+                        let name_line = if songlist.subscribed {
+                            Line::from(vec![Span::from("收藏 ").style(SUBSCRIBED_BADGE_STYLE), Span::from(songlist.name.clone())])
+                        } else {
+                            Line::from(songlist.name.clone())
+                        };
+                        Row::from_iter(vec![
+                            Cell::new(name_line),
+                            Cell::new(songlist.creator.clone()),
+                            Cell::new(format!("{:>6}", songlist.songs_count)),
+                        ])
+                    }
                 })
                 .collect();
 
             drop(player_guard);
 
-            // 防止悬空
-            self.songlists_table_state.select(None);
+            if is_first_load {
+                // 防止悬空
+                self.songlists_table_state.select(None);
+
+                self.scrollbar_state = ScrollbarState::new(self.songlists_table_rows.len());
+            } else {
+                self.scrollbar_state = self.scrollbar_state.content_length(self.songlists_table_rows.len());
 
-            self.scrollbar_state = ScrollbarState::new(self.songlists_table_rows.len());
+                // 删除歌单后防止越界
+                if let Some(selected) = self.songlists_table_state.selected() {
+                    if selected >= self.songlists_table_rows.len() {
+                        self.songlists_table_state.select(self.songlists_table_rows.len().checked_sub(1));
+                    }
+                }
+            }
 
             result = Ok(true);
+        } else {
+            drop(player_guard);
         }
 
         if self.songlists_table_state.selected() == None && !self.songlists_table_rows.is_empty() {
@@ -135,8 +171,8 @@ impl<'a> Controller for SonglistsPanel<'a> {
             .header(Row::new(vec![Cell::new("歌单"), Cell::new("创建者"), Cell::new("歌曲数")]).style(TABLE_HEADER_STYLE).height(1))
             .block({
                 let mut block = Block::default()
-                    .title(Line::from(format!("{}收藏的歌单", self.username)))
-                    .title_bottom(Line::from("按下`Alt+Enter`开始播放选中歌单").centered())
+                    .title(Line::from(format!("{}的歌单（创建 {} / 收藏 {}）", self.username, self.created_count, self.subscribed_count)))
+                    .title_bottom(Line::from("按下`Alt+Enter`开始播放选中歌单，按下`s`收藏/取消收藏").centered())
                     .borders(Borders::ALL);
                 if self.focused_status == PanelFocusedStatus::Outside {
                     block = block.border_style(PANEL_SELECTED_BORDER_STYLE);
@@ -1,11 +1,17 @@
 //
+mod album_screen;
+mod artist_screen;
 mod help_screen;
 mod login_screen;
 mod main_screen;
+mod search_screen;
 mod songlists_screen;
 
 //
+pub use album_screen::AlbumScreen;
+pub use artist_screen::ArtistScreen;
 pub use help_screen::HelpScreen;
 pub use login_screen::LoginScreen;
 pub use main_screen::MainScreen;
+pub use search_screen::SearchScreen;
 pub use songlists_screen::SonglistsScreen;
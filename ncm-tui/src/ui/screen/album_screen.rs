@@ -0,0 +1,232 @@
+use crate::config::style::*;
+use crate::config::{Command, ScreenEnum};
+use crate::ui::panel::{PanelFocusedStatus, PlaylistPanel};
+use crate::ui::Controller;
+use crate::{actions, command_queue, ncm_client, player};
+use anyhow::Result;
+use ncm_api::model::Album;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::prelude::{Line, Style, Text};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::Frame;
+
+#[derive(PartialEq)]
+enum Panels {
+    Info,
+    Songs,
+}
+
+#[derive(PartialEq)]
+enum FocusPanel {
+    InfoOutside,
+    SongsOutside,
+    SongsInside,
+}
+
+pub struct AlbumScreen<'a> {
+    // model
+    current_focus_panel: FocusPanel,
+    album: Option<Album>,
+
+    // view
+    album_info_page: Paragraph<'a>,
+    album_songs_panel: PlaylistPanel<'a>,
+}
+
+impl<'a> AlbumScreen<'a> {
+    pub fn new(_normal_style: &Style) -> Self {
+        Self {
+            current_focus_panel: FocusPanel::InfoOutside,
+            album: None,
+            album_info_page: Paragraph::default(),
+            album_songs_panel: PlaylistPanel::new(PanelFocusedStatus::Nop),
+        }
+    }
+}
+
+impl<'a> Controller for AlbumScreen<'a> {
+    async fn update_model(&mut self) -> Result<bool> {
+        self.album_songs_panel.update_model().await
+    }
+
+    async fn handle_event(&mut self, cmd: Command) -> Result<bool> {
+        use Command::*;
+        use FocusPanel::*;
+
+        match (cmd.clone(), &self.current_focus_panel) {
+            //
+            (OpenAlbum(album_id), _) => {
+                let album = ncm_client.lock().await.get_album(album_id).await?;
+
+                self.album_songs_panel.set_model(&album.name, &album.songs);
+                self.album = Some(album);
+                self.focus_panel_inside(Panels::Songs);
+            },
+            //
+            (Esc, SongsInside) => {
+                self.focus_panel_outside(Panels::Songs);
+            },
+            //
+            (Down | Up | EnterOrPlay, SongsOutside) => {
+                self.focus_panel_inside(Panels::Songs);
+            },
+            (Down | Up, SongsInside) => {
+                self.album_songs_panel.handle_event(cmd).await?;
+            },
+            //
+            (NextPanel, InfoOutside) => {
+                self.focus_panel_outside(Panels::Songs);
+            },
+            (PrevPanel, SongsOutside) => {
+                self.focus_panel_outside(Panels::Info);
+            },
+            // 以专辑作为播放列表，并从选中歌曲开始播放
+            (EnterOrPlay, SongsInside) => {
+                if let Some(album) = self.album.as_ref() {
+                    player.lock().await.switch_custom_playlist(format!("专辑: {}", album.name), album.songs.clone());
+
+                    // 播放选中歌曲
+                    self.album_songs_panel.handle_event(cmd).await?;
+
+                    // 返回 main_screen ，刷新播放列表显示
+                    let mut command_queue_guard = command_queue.lock().await;
+                    command_queue_guard.push_back(GotoScreen(ScreenEnum::Main));
+                    command_queue_guard.push_back(RefreshPlaylist);
+                    command_queue_guard.push_back(WhereIsThisSong);
+                    drop(command_queue_guard);
+                }
+            },
+            // 以专辑作为播放列表，并开始播放
+            (Play, _) => {
+                if let Some(album) = self.album.as_ref() {
+                    player.lock().await.switch_custom_playlist(format!("专辑: {}", album.name), album.songs.clone());
+
+                    // 开始自动播放，返回 main_screen ，刷新播放列表显示
+                    let mut command_queue_guard = command_queue.lock().await;
+                    command_queue_guard.push_back(StartPlay);
+                    command_queue_guard.push_back(GotoScreen(ScreenEnum::Main));
+                    command_queue_guard.push_back(RefreshPlaylist);
+                    command_queue_guard.push_back(WhereIsThisSong);
+                    drop(command_queue_guard);
+                }
+            },
+            //
+            (GoToTop | GoToBottom, _) => {
+                self.album_songs_panel.handle_event(cmd).await?;
+                self.focus_panel_inside(Panels::Songs);
+            },
+            //
+            (Like(_) | ToggleLike, SongsInside) => {
+                self.album_songs_panel.handle_event(cmd).await?;
+            },
+            (Like(like), _) => {
+                actions::like_current_song(Some(like)).await?;
+            },
+            (ToggleLike, _) => {
+                actions::like_current_song(None).await?;
+            },
+            //
+            (ViewAlbum, SongsInside) => {
+                actions::view_album(self.album_songs_panel.get_selected_song()).await;
+            },
+            (ViewAlbum, _) => {
+                actions::view_album(None).await;
+            },
+            //
+            (AddToSonglist(songlist_name), SongsInside) => {
+                actions::add_to_songlist(&songlist_name, self.album_songs_panel.get_selected_song()).await?;
+            },
+            (AddToSonglist(songlist_name), _) => {
+                actions::add_to_songlist(&songlist_name, None).await?;
+            },
+            //
+            (ViewArtist(artist_index), SongsInside) => {
+                actions::view_artist(self.album_songs_panel.get_selected_song(), artist_index).await?;
+            },
+            // 未选中歌曲时打开专辑歌手的主页
+            (ViewArtist(artist_index), _) => match self.album.as_ref() {
+                Some(album) => command_queue.lock().await.push_back(OpenArtist(album.artist_id)),
+                None => actions::view_artist(None, artist_index).await?,
+            },
+            //
+            (_, _) => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn update_view(&mut self, style: &Style) {
+        let album_info_text = match self.album.as_ref() {
+            Some(album) => {
+                let mut lines = vec![
+                    Line::from(format!("\u{1F4DA}{}", album.name)).style(LYRIC_FOCUSED_STYLE),
+                    Line::from(format!("\u{1F3A4}{}", album.artist)),
+                    Line::from(format!("发行时间: {}", album.publish_date())),
+                    Line::from(format!("歌曲数: {}", album.songs.len())),
+                    Line::from(""),
+                ];
+                lines.extend(album.description.lines().map(|line| Line::from(line.to_string())));
+                Text::from(lines)
+            },
+            None => Text::from(Line::from("在歌曲列表中选中歌曲后输入\":album\"查看所属专辑").centered()),
+        };
+
+        self.album_info_page = Paragraph::new(album_info_text)
+            .block({
+                let mut block = Block::default()
+                    .title(Line::from("专辑信息"))
+                    .title_bottom(Line::from("按下`Alt+Enter`播放整张专辑").centered())
+                    .borders(Borders::ALL);
+                if self.current_focus_panel == FocusPanel::InfoOutside {
+                    block = block.border_style(PANEL_SELECTED_BORDER_STYLE);
+                }
+
+                block
+            })
+            .wrap(Wrap { trim: false })
+            .style(*style);
+
+        self.album_songs_panel.update_view(style);
+    }
+
+    fn draw(&self, frame: &mut Frame, chunk: Rect) {
+        // 分为左右两个面板
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(35), Constraint::Percentage(65)].as_ref())
+            .split(chunk);
+
+        // 在左侧渲染专辑信息
+        frame.render_widget(&self.album_info_page, chunks[0]);
+
+        // 在右侧渲染专辑歌曲
+        self.album_songs_panel.draw(frame, chunks[1]);
+    }
+}
+
+/// private
+impl<'a> AlbumScreen<'a> {
+    fn focus_panel_outside(&mut self, to_panel: Panels) {
+        match to_panel {
+            Panels::Info => {
+                self.current_focus_panel = FocusPanel::InfoOutside;
+                self.album_songs_panel.focused_status = PanelFocusedStatus::Nop;
+            },
+            Panels::Songs => {
+                self.current_focus_panel = FocusPanel::SongsOutside;
+                self.album_songs_panel.focused_status = PanelFocusedStatus::Outside;
+            },
+        }
+    }
+
+    fn focus_panel_inside(&mut self, to_panel: Panels) {
+        match to_panel {
+            // 专辑信息面板不可进入
+            Panels::Info => self.focus_panel_outside(Panels::Info),
+            Panels::Songs => {
+                self.current_focus_panel = FocusPanel::SongsInside;
+                self.album_songs_panel.focused_status = PanelFocusedStatus::Inside;
+            },
+        }
+    }
+}
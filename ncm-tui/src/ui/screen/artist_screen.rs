@@ -0,0 +1,376 @@
+use crate::config::style::*;
+use crate::config::{Command, ScreenEnum};
+use crate::ui::panel::{ArtistAlbumsPanel, PanelFocusedStatus, PlaylistPanel, SimilarArtistsPanel};
+use crate::ui::Controller;
+use crate::{actions, command_queue, ncm_client, player};
+use anyhow::Result;
+use log::error;
+use ncm_api::model::{Artist, Song};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::prelude::{Line, Style, Text};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::Frame;
+
+#[derive(PartialEq)]
+enum Panels {
+    Info,
+    Albums,
+    SimilarArtists,
+    TopSongs,
+}
+
+#[derive(PartialEq)]
+enum FocusPanel {
+    InfoOutside,
+    AlbumsOutside,
+    AlbumsInside,
+    SimilarArtistsOutside,
+    SimilarArtistsInside,
+    TopSongsOutside,
+    TopSongsInside,
+}
+
+pub struct ArtistScreen<'a> {
+    // model
+    current_focus_panel: FocusPanel,
+    artist: Option<Artist>,
+    top_songs: Vec<Song>,
+
+    // view
+    artist_info_page: Paragraph<'a>,
+    albums_panel: ArtistAlbumsPanel<'a>,
+    similar_artists_panel: SimilarArtistsPanel<'a>,
+    top_songs_panel: PlaylistPanel<'a>,
+}
+
+impl<'a> ArtistScreen<'a> {
+    pub fn new(_normal_style: &Style) -> Self {
+        Self {
+            current_focus_panel: FocusPanel::InfoOutside,
+            artist: None,
+            top_songs: Vec::new(),
+            artist_info_page: Paragraph::default(),
+            albums_panel: ArtistAlbumsPanel::new(PanelFocusedStatus::Nop),
+            similar_artists_panel: SimilarArtistsPanel::new(PanelFocusedStatus::Nop),
+            top_songs_panel: PlaylistPanel::new(PanelFocusedStatus::Nop),
+        }
+    }
+}
+
+impl<'a> Controller for ArtistScreen<'a> {
+    async fn update_model(&mut self) -> Result<bool> {
+        let mut result = Ok(false);
+
+        // albums
+        if self.albums_panel.update_model().await? {
+            result = Ok(true);
+        }
+
+        // similar artists
+        if self.similar_artists_panel.update_model().await? {
+            result = Ok(true);
+        }
+
+        // top songs
+        if self.top_songs_panel.update_model().await? {
+            result = Ok(true);
+        }
+
+        result
+    }
+
+    async fn handle_event(&mut self, cmd: Command) -> Result<bool> {
+        use Command::*;
+        use FocusPanel::*;
+
+        match (cmd.clone(), &self.current_focus_panel) {
+            //
+            (OpenArtist(artist_id), _) => {
+                self.open_artist(artist_id).await?;
+                self.focus_panel_inside(Panels::TopSongs);
+            },
+            //
+            (Esc, AlbumsInside) => {
+                self.focus_panel_outside(Panels::Albums);
+            },
+            (Esc, SimilarArtistsInside) => {
+                self.focus_panel_outside(Panels::SimilarArtists);
+            },
+            (Esc, TopSongsInside) => {
+                self.focus_panel_outside(Panels::TopSongs);
+            },
+            //
+            (Down | Up | EnterOrPlay, AlbumsOutside) => {
+                self.focus_panel_inside(Panels::Albums);
+            },
+            (Down | Up | EnterOrPlay, SimilarArtistsOutside) => {
+                self.focus_panel_inside(Panels::SimilarArtists);
+            },
+            (Down | Up | EnterOrPlay, TopSongsOutside) => {
+                self.focus_panel_inside(Panels::TopSongs);
+            },
+            (Down | Up, AlbumsInside) => {
+                self.albums_panel.handle_event(cmd).await?;
+            },
+            (Down | Up, SimilarArtistsInside) => {
+                self.similar_artists_panel.handle_event(cmd).await?;
+            },
+            (Down | Up, TopSongsInside) => {
+                self.top_songs_panel.handle_event(cmd).await?;
+            },
+            //
+            (NextPanel, InfoOutside) => {
+                self.focus_panel_outside(Panels::Albums);
+            },
+            (NextPanel, AlbumsOutside) => {
+                self.focus_panel_outside(Panels::SimilarArtists);
+            },
+            (NextPanel, SimilarArtistsOutside) => {
+                self.focus_panel_outside(Panels::TopSongs);
+            },
+            (PrevPanel, AlbumsOutside) => {
+                self.focus_panel_outside(Panels::Info);
+            },
+            (PrevPanel, SimilarArtistsOutside) => {
+                self.focus_panel_outside(Panels::Albums);
+            },
+            (PrevPanel, TopSongsOutside) => {
+                self.focus_panel_outside(Panels::SimilarArtists);
+            },
+            // 打开选中专辑的详情页
+            (EnterOrPlay, AlbumsInside) => {
+                if let Some(album) = self.albums_panel.get_selected_album() {
+                    command_queue.lock().await.push_back(OpenAlbum(album.id));
+                }
+            },
+            // 以选中专辑作为播放列表，并开始播放
+            (Play, AlbumsInside) => {
+                if let Some(album) = self.albums_panel.get_selected_album() {
+                    let album = ncm_client.lock().await.get_album(album.id).await?;
+                    player.lock().await.switch_custom_playlist(format!("专辑: {}", album.name), album.songs);
+
+                    // 开始自动播放，返回 main_screen ，刷新播放列表显示
+                    let mut command_queue_guard = command_queue.lock().await;
+                    command_queue_guard.push_back(StartPlay);
+                    command_queue_guard.push_back(GotoScreen(ScreenEnum::Main));
+                    command_queue_guard.push_back(RefreshPlaylist);
+                    command_queue_guard.push_back(WhereIsThisSong);
+                    drop(command_queue_guard);
+                }
+            },
+            // 打开选中的相似歌手
+            (EnterOrPlay | Play, SimilarArtistsInside) => {
+                if let Some(artist) = self.similar_artists_panel.get_selected_artist() {
+                    command_queue.lock().await.push_back(OpenArtist(artist.id));
+                }
+            },
+            // 以热门歌曲作为播放列表，并从选中歌曲开始播放
+            (EnterOrPlay, TopSongsInside) => {
+                if let Some(artist) = self.artist.as_ref() {
+                    player.lock().await.switch_custom_playlist(format!("歌手: {}", artist.name), self.top_songs.clone());
+
+                    // 播放选中歌曲
+                    self.top_songs_panel.handle_event(cmd).await?;
+
+                    // 返回 main_screen ，刷新播放列表显示
+                    let mut command_queue_guard = command_queue.lock().await;
+                    command_queue_guard.push_back(GotoScreen(ScreenEnum::Main));
+                    command_queue_guard.push_back(RefreshPlaylist);
+                    command_queue_guard.push_back(WhereIsThisSong);
+                    drop(command_queue_guard);
+                }
+            },
+            // 以热门歌曲作为播放列表，并开始播放
+            (Play, _) => {
+                if let Some(artist) = self.artist.as_ref() {
+                    player.lock().await.switch_custom_playlist(format!("歌手: {}", artist.name), self.top_songs.clone());
+
+                    // 开始自动播放，返回 main_screen ，刷新播放列表显示
+                    let mut command_queue_guard = command_queue.lock().await;
+                    command_queue_guard.push_back(StartPlay);
+                    command_queue_guard.push_back(GotoScreen(ScreenEnum::Main));
+                    command_queue_guard.push_back(RefreshPlaylist);
+                    command_queue_guard.push_back(WhereIsThisSong);
+                    drop(command_queue_guard);
+                }
+            },
+            //
+            (GoToTop | GoToBottom, AlbumsOutside | AlbumsInside) => {
+                self.albums_panel.handle_event(cmd).await?;
+                self.focus_panel_inside(Panels::Albums);
+            },
+            (GoToTop | GoToBottom, SimilarArtistsOutside | SimilarArtistsInside) => {
+                self.similar_artists_panel.handle_event(cmd).await?;
+                self.focus_panel_inside(Panels::SimilarArtists);
+            },
+            (GoToTop | GoToBottom, _) => {
+                self.top_songs_panel.handle_event(cmd).await?;
+                self.focus_panel_inside(Panels::TopSongs);
+            },
+            //
+            (Like(_) | ToggleLike, TopSongsInside) => {
+                self.top_songs_panel.handle_event(cmd).await?;
+            },
+            (Like(like), _) => {
+                actions::like_current_song(Some(like)).await?;
+            },
+            (ToggleLike, _) => {
+                actions::like_current_song(None).await?;
+            },
+            //
+            (ViewAlbum, TopSongsInside) => {
+                actions::view_album(self.top_songs_panel.get_selected_song()).await;
+            },
+            (ViewAlbum, _) => {
+                actions::view_album(None).await;
+            },
+            //
+            (ViewArtist(artist_index), TopSongsInside) => {
+                actions::view_artist(self.top_songs_panel.get_selected_song(), artist_index).await?;
+            },
+            (ViewArtist(artist_index), _) => {
+                actions::view_artist(None, artist_index).await?;
+            },
+            //
+            (AddToSonglist(songlist_name), TopSongsInside) => {
+                actions::add_to_songlist(&songlist_name, self.top_songs_panel.get_selected_song()).await?;
+            },
+            (AddToSonglist(songlist_name), _) => {
+                actions::add_to_songlist(&songlist_name, None).await?;
+            },
+            //
+            (_, _) => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn update_view(&mut self, style: &Style) {
+        let artist_info_text = match self.artist.as_ref() {
+            Some(artist) => {
+                let mut lines = vec![
+                    Line::from(format!("\u{1F3A4}{}", artist.name)).style(LYRIC_FOCUSED_STYLE),
+                    Line::from(format!("专辑数: {}    歌曲数: {}", artist.albums_count, artist.songs_count)),
+                    Line::from(""),
+                ];
+                lines.extend(artist.description.lines().map(|line| Line::from(line.to_string())));
+                Text::from(lines)
+            },
+            None => Text::from(Line::from("在歌曲列表中选中歌曲后输入\":artist\"查看歌手主页").centered()),
+        };
+
+        self.artist_info_page = Paragraph::new(artist_info_text)
+            .block({
+                let mut block = Block::default()
+                    .title(Line::from("歌手信息"))
+                    .title_bottom(Line::from("按下`Alt+Enter`播放热门歌曲").centered())
+                    .borders(Borders::ALL);
+                if self.current_focus_panel == FocusPanel::InfoOutside {
+                    block = block.border_style(PANEL_SELECTED_BORDER_STYLE);
+                }
+
+                block
+            })
+            .wrap(Wrap { trim: false })
+            .style(*style);
+
+        self.albums_panel.update_view(style);
+        self.similar_artists_panel.update_view(style);
+        self.top_songs_panel.update_view(style);
+    }
+
+    fn draw(&self, frame: &mut Frame, chunk: Rect) {
+        // 分为左右两部分
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)].as_ref())
+            .split(chunk);
+
+        // 左侧从上到下依次为歌手信息、专辑、相似歌手
+        let left_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(8), Constraint::Min(5), Constraint::Length(8)].as_ref())
+            .split(chunks[0]);
+
+        frame.render_widget(&self.artist_info_page, left_chunks[0]);
+        self.albums_panel.draw(frame, left_chunks[1]);
+        self.similar_artists_panel.draw(frame, left_chunks[2]);
+
+        // 在右侧渲染热门歌曲
+        self.top_songs_panel.draw(frame, chunks[1]);
+    }
+}
+
+/// private
+impl<'a> ArtistScreen<'a> {
+    /// 加载歌手详情、热门歌曲、专辑和相似歌手
+    async fn open_artist(&mut self, artist_id: u64) -> Result<()> {
+        let ncm_client_guard = ncm_client.lock().await;
+        let artist = ncm_client_guard.get_artist_detail(artist_id).await?;
+        let top_songs = ncm_client_guard.get_artist_top_songs(artist_id).await?;
+        // 相似歌手需要登录，获取失败时不影响歌手主页的显示
+        let similar_artists = ncm_client_guard.get_similar_artists(artist_id).await.unwrap_or_else(|err| {
+            error!("failed to get similar artists: {:?}", err);
+            Vec::new()
+        });
+        drop(ncm_client_guard);
+
+        self.albums_panel.load_artist_albums(artist_id).await?;
+        self.similar_artists_panel.set_model(similar_artists);
+        self.top_songs_panel.set_model(&format!("热门歌曲: {}", artist.name), &top_songs);
+
+        self.artist = Some(artist);
+        self.top_songs = top_songs;
+
+        Ok(())
+    }
+
+    fn focus_panel_outside(&mut self, to_panel: Panels) {
+        self.albums_panel.focused_status = PanelFocusedStatus::Nop;
+        self.similar_artists_panel.focused_status = PanelFocusedStatus::Nop;
+        self.top_songs_panel.focused_status = PanelFocusedStatus::Nop;
+
+        match to_panel {
+            Panels::Info => {
+                self.current_focus_panel = FocusPanel::InfoOutside;
+            },
+            Panels::Albums => {
+                self.current_focus_panel = FocusPanel::AlbumsOutside;
+                self.albums_panel.focused_status = PanelFocusedStatus::Outside;
+            },
+            Panels::SimilarArtists => {
+                self.current_focus_panel = FocusPanel::SimilarArtistsOutside;
+                self.similar_artists_panel.focused_status = PanelFocusedStatus::Outside;
+            },
+            Panels::TopSongs => {
+                self.current_focus_panel = FocusPanel::TopSongsOutside;
+                self.top_songs_panel.focused_status = PanelFocusedStatus::Outside;
+            },
+        }
+    }
+
+    fn focus_panel_inside(&mut self, to_panel: Panels) {
+        self.albums_panel.focused_status = PanelFocusedStatus::Nop;
+        self.similar_artists_panel.focused_status = PanelFocusedStatus::Nop;
+        self.top_songs_panel.focused_status = PanelFocusedStatus::Nop;
+
+        match to_panel {
+            // 歌手信息面板不可进入
+            Panels::Info => {
+                self.current_focus_panel = FocusPanel::InfoOutside;
+            },
+            Panels::Albums => {
+                self.current_focus_panel = FocusPanel::AlbumsInside;
+                self.albums_panel.focused_status = PanelFocusedStatus::Inside;
+            },
+            Panels::SimilarArtists => {
+                self.current_focus_panel = FocusPanel::SimilarArtistsInside;
+                self.similar_artists_panel.focused_status = PanelFocusedStatus::Inside;
+            },
+            Panels::TopSongs => {
+                self.current_focus_panel = FocusPanel::TopSongsInside;
+                self.top_songs_panel.focused_status = PanelFocusedStatus::Inside;
+            },
+        }
+    }
+}
@@ -22,14 +22,20 @@ impl<'a> HelpScreen<'a> {
             Previous Panel:                         {}\n\
             Next Panel:                             {}\n\
             Go To Main Screen:                      {}\n\
+            Go To Songlists Screen:                 {}\n\
+            Go To Search Screen:                    {}\n\
             Go To Help Screen (Here):               {}\n\
             Play Next Song:                         {}\n\
             Play Previous Song:                     {}\n\
             *Switch To Command Line Mode:           {}\n\
             Search Forward:                         {}\n\
             Search Backward:                        {}\n\
+            Switch Search Type (Search Screen):     {}\n\
+            Like / Unlike Selected Song:            {}\n\
+            Subscribe / Unsubscribe Songlist:       {}\n\
+            Show Lyrics / Comments (Main Screen):   {}\n\
             Quit:                                   {}",
-            "↑ / k", "↓ / j", "\u{2423} (Space)", "←", "→", "1", "0 / F1", ">", "<", ":", "/", "?", "q",
+            "↑ / k", "↓ / j", "\u{2423} (Space)", "←", "→", "1", "2", "3", "0 / F1", ">", "<", ":", "/", "?", "t", "f", "s", "c", "q",
         ));
         let normal_mode_help_page = Paragraph::new(normal_mode_help_text)
             .block(Block::default().title("普通模式").borders(Borders::ALL))
@@ -42,9 +48,11 @@ impl<'a> HelpScreen<'a> {
             |_                                      {}\n\
             Go To Help Screen (Here):               {}\n\
             Go To Login Screen:                     {}\n\
-            Logout:                                 {}\n\
+            |_ switch login method:                 ← / → (on login screen)\n\
+            Logout:                                 {} (`logout purge` will also clear lyrics cache)\n\
             Set Volume:                             {} (e.g. `vol 20` will set volume at 20%)\n\
             Mute:                                   {}\n\
+            Set Preferred Quality:                  {} (falls back to lower quality if unavailable)\n\
             Set Play Mode:                          {}\n\
             |_ single play mode:                    {}\n\
             |_ single repeat mode:                  {}\n\
@@ -52,20 +60,42 @@ impl<'a> HelpScreen<'a> {
             |_ shuffle mode:                        {}\n\
             Play Next Song:                         {}\n\
             Play Previous Song:                     {}\n\
-            Start Auto Play:                        {} (Only under `list repeat mode` or `shuffle mode`)\n\
+            Start Auto Play:                        {} (Only under `list repeat mode`, `shuffle mode`, `personal fm` or `heartbeat mode`)\n\
+            Start Personal FM:                      {}\n\
+            |_ dislike current song (skip):         {}\n\
+            Start Heartbeat Mode:                   {} (recommended songs are marked with \u{2726})\n\
+            Start Radio From Current Song:          {} (similar songs, keeps extending under `list repeat mode`)\n\
             Jump To Current Song In Playlist:       {}\n\
             Jump To Top:                            {}\n\
             Jump To Bottom:                         {}\n\
             Search Forward:                         {}\n\
-            Search Backward:                        {}",
+            Search Backward:                        {}\n\
+            Search Songs Globally:                  {}\n\
+            Like Selected Song:                     {}\n\
+            Unlike Selected Song:                   {}\n\
+            View Album Of Selected Song:            {}\n\
+            View Artist Of Selected Song:           {} (`artist 2` opens the 2nd artist of a collaboration)\n\
+            Create Songlist:                        {}\n\
+            Delete Selected Songlist:               {} (on songlists screen)\n\
+            Rename Selected Songlist:               {} (on songlists screen)\n\
+            Add Selected Song To Songlist:          {}\n\
+            Remove Selected Song From Songlist:     {}\n\
+            Subscribe Selected Songlist:            {}\n\
+            Unsubscribe Selected Songlist:          {}\n\
+            Show / Hide Comments Of Current Song:   {}\n\
+            |_ sort by recommend / hot / time:      {}\n\
+            Comment On Current Song:                {}\n\
+            Reply To Selected Comment:              {} (on comments panel)\n\
+            Upload Local Audio File To Cloud:       {} (progress shown in the bottom bar)",
             "q / quit / exit",
-            "screen 0 / 1",
-            "screen help / main",
+            "screen 0 / 1 / 2 / 3",
+            "screen help / main / playlists / search",
             "h / help",
             "l / login",
             "logout",
             "vol / volume",
             "mute",
+            "quality standard / higher / exhigh / lossless / hires / jyeffect / sky / dolby / jymaster",
             "mode",
             "mode single",
             "mode sr / single-repeat",
@@ -74,11 +104,32 @@ impl<'a> HelpScreen<'a> {
             "next",
             "prev / previous",
             "start",
+            "fm",
+            "fm trash / dislike",
+            "hb / heartbeat / intelligence",
+            "radio",
             "where this",
             "top",
             "bottom",
             "/ xxx",
             "? xxx",
+            "search xxx",
+            "like",
+            "unlike",
+            "album",
+            "artist [N]",
+            "songlist new xxx",
+            "songlist delete xxx",
+            "songlist rename xxx",
+            "add-to xxx",
+            "remove",
+            "subscribe / sub",
+            "unsubscribe / unsub",
+            "comments",
+            "comments rec / hot / new",
+            "comment xxx",
+            "reply xxx",
+            "upload /path/to/file",
         ));
         let commandline_mode_help_page = Paragraph::new(commandline_mode_help_text)
             .block(Block::default().title("命令行模式").borders(Borders::ALL))
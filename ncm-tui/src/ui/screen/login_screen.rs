@@ -1,26 +1,74 @@
+use crate::config::style::*;
 use crate::config::Command;
-use crate::ncm_client;
+use crate::{command_queue, ncm_client};
 use crate::ui::Controller;
 use anyhow::Result;
+use crossterm::event::KeyEvent;
 use fast_qr::QRBuilder;
 use log::debug;
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Tabs},
 };
+use tui_textarea::TextArea;
+
+/// 登录方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginMethod {
+    Qr,
+    CellphonePassword,
+    CellphoneCaptcha,
+    Email,
+}
+
+impl LoginMethod {
+    const ALL: [LoginMethod; 4] = [LoginMethod::Qr, LoginMethod::CellphonePassword, LoginMethod::CellphoneCaptcha, LoginMethod::Email];
+
+    fn name(&self) -> &'static str {
+        match self {
+            LoginMethod::Qr => "二维码登录",
+            LoginMethod::CellphonePassword => "手机号+密码",
+            LoginMethod::CellphoneCaptcha => "手机号+验证码",
+            LoginMethod::Email => "邮箱+密码",
+        }
+    }
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|method| method == self).unwrap_or(0)
+    }
+}
+
+/// 表单中可聚焦的项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FormItem {
+    Account,
+    Secret,
+    SendCaptcha,
+    Submit,
+}
 
 pub struct LoginScreen<'a> {
     // model
+    login_method: LoginMethod,     // 当前登录方式
     login_url: String,             // 登录 url
     login_unikey: String,          // 登录 url 校验码
     login_qr_lines: Vec<Line<'a>>, // 登录二维码（按行）
     login_qr_status_code: usize,   // 登录二维码状态码
     login_qr_status: String,       // 登录二维码状态
+    is_login_ok: bool,             // 是否已登录成功（二维码确认或表单提交成功），之后进行 cookie 保存
     is_login_ok_refreshed: bool,   // 标志控制位，控制登录完成后第一次 update_model 更新“登录成功”的信息，第二次 update_model 才进行 cookie 保存等高延迟操作
     tick_tok: usize,               // 时钟记录，0~3，用于在状态显示后增加动态省略号，也用于控制发送检查二维码请求的频率
+    //
+    account_input: TextArea<'a>, // 手机号/邮箱
+    secret_input: TextArea<'a>,  // 密码/验证码
+    form_focus: FormItem,        // 表单中聚焦的项
+    inputting: bool,             // 是否正在向输入框输入
+    form_status: String,         // 表单提交状态
 
     // view
+    method_tabs: Tabs<'a>,
     login_page: Paragraph<'a>,
+    form_footer: Paragraph<'a>, // 表单按钮和提交状态
 }
 
 impl<'a> LoginScreen<'a> {
@@ -30,19 +78,41 @@ impl<'a> LoginScreen<'a> {
         let login_qrcode_status = String::from("二维码获取中");
 
         let mut s = Self {
+            login_method: LoginMethod::Qr,
             login_url: login_qr_url,
             login_unikey,
             login_qr_lines: vec![Line::from("「...」").centered()],
             login_qr_status_code: 0,
             login_qr_status: login_qrcode_status,
-            login_page: Paragraph::default(),
+            is_login_ok: false,
             is_login_ok_refreshed: false,
             tick_tok: 0,
+            account_input: TextArea::default(),
+            secret_input: TextArea::default(),
+            form_focus: FormItem::Account,
+            inputting: false,
+            form_status: String::new(),
+            method_tabs: Tabs::default(),
+            login_page: Paragraph::default(),
+            form_footer: Paragraph::default(),
         };
         s.update_view(normal_style);
         s
     }
 
+    /// 输入框模式下的按键输入
+    pub fn input(&mut self, key_event: KeyEvent) {
+        match self.form_focus {
+            FormItem::Account => {
+                self.account_input.input(key_event);
+            },
+            FormItem::Secret => {
+                self.secret_input.input(key_event);
+            },
+            _ => {},
+        }
+    }
+
     async fn create_login_qr(&mut self) -> Result<()> {
         let (qr_unikey, qr_url) = ncm_client.lock().await.get_login_qr().await?;
 
@@ -55,6 +125,94 @@ impl<'a> LoginScreen<'a> {
 
         Ok(())
     }
+
+    /// 当前登录方式下表单中可聚焦的项
+    fn form_items(&self) -> Vec<FormItem> {
+        match self.login_method {
+            LoginMethod::Qr => Vec::new(),
+            LoginMethod::CellphoneCaptcha => vec![FormItem::Account, FormItem::Secret, FormItem::SendCaptcha, FormItem::Submit],
+            _ => vec![FormItem::Account, FormItem::Secret, FormItem::Submit],
+        }
+    }
+
+    fn switch_login_method(&mut self, login_method: LoginMethod) {
+        self.login_method = login_method;
+        self.form_focus = FormItem::Account;
+        self.inputting = false;
+        self.form_status = String::new();
+        self.secret_input = TextArea::default();
+    }
+
+    fn move_form_focus(&mut self, step: isize) {
+        let form_items = self.form_items();
+        if let Some(idx) = form_items.iter().position(|item| *item == self.form_focus) {
+            let next_idx = (idx as isize + step).clamp(0, form_items.len() as isize - 1) as usize;
+            self.form_focus = form_items[next_idx];
+        }
+    }
+
+    /// 提交表单登录，成功后与二维码登录一样进行 cookie 保存
+    async fn submit_form(&mut self) {
+        let account = self.account_input.lines()[0].trim().to_string();
+        let secret = self.secret_input.lines()[0].clone();
+        if account.is_empty() || secret.is_empty() {
+            self.form_status = String::from("请填写完整的登录信息");
+            return;
+        }
+
+        let mut ncm_client_guard = ncm_client.lock().await;
+        let login_result = match self.login_method {
+            LoginMethod::CellphonePassword => ncm_client_guard.login_by_cellphone_password(&account, &secret).await,
+            LoginMethod::CellphoneCaptcha => ncm_client_guard.login_by_cellphone_captcha(&account, &secret).await,
+            LoginMethod::Email => ncm_client_guard.login_by_email(&account, &secret).await,
+            LoginMethod::Qr => return,
+        };
+
+        match login_result {
+            Ok(_) => {
+                self.form_status = String::from("登录成功，请稍等");
+                self.is_login_ok = true;
+            },
+            Err(err) => {
+                self.form_status = err.to_string();
+            },
+        }
+    }
+
+    async fn send_captcha(&mut self) {
+        let phone = self.account_input.lines()[0].trim().to_string();
+        if phone.is_empty() {
+            self.form_status = String::from("请先填写手机号");
+            return;
+        }
+
+        self.form_status = match ncm_client.lock().await.send_captcha(&phone, "86").await {
+            Ok(_) => String::from("验证码已发送"),
+            Err(err) => err.to_string(),
+        };
+    }
+
+    /// 以当前聚焦状态生成输入框
+    fn styled_input(&self, mut input: TextArea<'a>, item: FormItem, title: &'a str) -> TextArea<'a> {
+        let focused = self.form_focus == item;
+        let mut block = Block::default().title(Line::from(title)).borders(Borders::ALL);
+        if focused {
+            block = block.border_style(PANEL_SELECTED_BORDER_STYLE);
+        }
+        input.set_block(block);
+        input.set_cursor_line_style(Style::default());
+        input.set_cursor_style(if focused && self.inputting { Style::default().add_modifier(Modifier::REVERSED) } else { Style::default().add_modifier(Modifier::HIDDEN) });
+
+        input
+    }
+
+    fn form_button(&self, item: FormItem, text: &'a str) -> Span<'a> {
+        if self.form_focus == item {
+            Span::from(text).style(ITEM_SELECTED_STYLE)
+        } else {
+            Span::from(text)
+        }
+    }
 }
 
 impl<'a> Controller for LoginScreen<'a> {
@@ -65,6 +223,28 @@ impl<'a> Controller for LoginScreen<'a> {
             self.tick_tok = 0;
         }
 
+        // 登录成功
+        if self.is_login_ok {
+            if !self.is_login_ok_refreshed {
+                // 登录成功后第一次 update_model
+                // 不阻塞，以便 update_view 能够及时显示“登录成功”
+                self.is_login_ok_refreshed = true;
+            } else {
+                // 登录成功后第二次 update_model
+                debug!("login successfully, start cookie storing...");
+                let mut ncm_client_guard = ncm_client.lock().await;
+                ncm_client_guard.store_cookie();
+                ncm_client_guard.check_login_status().await?;
+            }
+
+            return Ok(true);
+        }
+
+        // 表单登录时不轮询二维码状态
+        if self.login_method != LoginMethod::Qr {
+            return Ok(false);
+        }
+
         // 初始化
         if self.login_url == "" || self.login_unikey == "" {
             self.create_login_qr().await?;
@@ -98,43 +278,125 @@ impl<'a> Controller for LoginScreen<'a> {
             tokio::time::sleep(std::time::Duration::from_millis(350)).await;
         }
 
-        // 登录成功
         if self.login_qr_status_code == 803 {
-            if !self.is_login_ok_refreshed {
-                // 登录成功后第一次 update_model
-                // 不阻塞，以便 update_view 能够及时显示“登录成功”
-                self.is_login_ok_refreshed = true;
-            } else {
-                // 登录成功后第二次 update_model
-                debug!("login successfully, start cookie storing...");
-                let mut ncm_client_guard = ncm_client.lock().await;
-                ncm_client_guard.store_cookie();
-                ncm_client_guard.check_login_status().await?;
-            }
+            self.is_login_ok = true;
         }
 
         Ok(true)
     }
 
-    async fn handle_event(&mut self, _cmd: Command) -> Result<bool> {
-        Ok(false)
+    async fn handle_event(&mut self, cmd: Command) -> Result<bool> {
+        // 登录成功后不再响应
+        if self.is_login_ok {
+            return Ok(false);
+        }
+
+        match cmd {
+            Command::NextPanel => {
+                let next_idx = (self.login_method.index() + 1) % LoginMethod::ALL.len();
+                self.switch_login_method(LoginMethod::ALL[next_idx]);
+            },
+            Command::PrevPanel => {
+                let prev_idx = (self.login_method.index() + LoginMethod::ALL.len() - 1) % LoginMethod::ALL.len();
+                self.switch_login_method(LoginMethod::ALL[prev_idx]);
+            },
+            Command::Down => {
+                self.inputting = false;
+                self.move_form_focus(1);
+            },
+            Command::Up => {
+                self.inputting = false;
+                self.move_form_focus(-1);
+            },
+            Command::Esc => {
+                self.inputting = false;
+            },
+            Command::EnterOrPlay if self.login_method != LoginMethod::Qr => match self.form_focus {
+                FormItem::Account | FormItem::Secret => {
+                    self.inputting = true;
+                    command_queue.lock().await.push_back(Command::EnterInputMode);
+                },
+                FormItem::SendCaptcha => {
+                    self.send_captcha().await;
+                },
+                FormItem::Submit => {
+                    self.submit_form().await;
+                },
+            },
+            _ => return Ok(false),
+        }
+
+        Ok(true)
     }
 
     fn update_view(&mut self, style: &Style) {
-        let login_text = Text::from(self.login_qr_lines.clone());
-
-        self.login_page = Paragraph::new(login_text)
-            .block(
-                Block::default()
-                    .title(Line::from("Netease Cloud Music - QR Code Login").left_aligned())
-                    .title(Line::from(format!("{}{}", self.login_qr_status.clone(), ".".repeat(self.tick_tok))).right_aligned())
-                    .title_bottom(Line::from("如果无法识别二维码，可将终端背景色改为深色后再尝试").right_aligned())
-                    .borders(Borders::ALL),
-            )
+        self.method_tabs = Tabs::new(LoginMethod::ALL.iter().map(|method| method.name()))
+            .highlight_style(ITEM_SELECTED_STYLE)
+            .select(self.login_method.index())
             .style(*style);
+
+        if self.login_method == LoginMethod::Qr {
+            let login_text = Text::from(self.login_qr_lines.clone());
+
+            self.login_page = Paragraph::new(login_text)
+                .block(
+                    Block::default()
+                        .title(Line::from("Netease Cloud Music - QR Code Login").left_aligned())
+                        .title(Line::from(format!("{}{}", self.login_qr_status.clone(), ".".repeat(self.tick_tok))).right_aligned())
+                        .title_bottom(Line::from("如果无法识别二维码，可将终端背景色改为深色后再尝试").right_aligned())
+                        .borders(Borders::ALL),
+                )
+                .style(*style);
+        } else {
+            let (account_title, secret_title) = match self.login_method {
+                LoginMethod::Email => ("邮箱", "密码"),
+                LoginMethod::CellphoneCaptcha => ("手机号", "验证码"),
+                _ => ("手机号", "密码"),
+            };
+
+            // 密码输入框使用掩码，验证码输入框不使用
+            let mut secret_input = self.secret_input.clone();
+            if self.login_method == LoginMethod::CellphoneCaptcha {
+                secret_input.clear_mask_char();
+            } else {
+                secret_input.set_mask_char('\u{2022}');
+            }
+            self.account_input = self.styled_input(self.account_input.clone(), FormItem::Account, account_title);
+            self.secret_input = self.styled_input(secret_input, FormItem::Secret, secret_title);
+
+            let mut buttons = Vec::new();
+            if self.login_method == LoginMethod::CellphoneCaptcha {
+                buttons.push(self.form_button(FormItem::SendCaptcha, " 发送验证码 "));
+                buttons.push(Span::from("    "));
+            }
+            buttons.push(self.form_button(FormItem::Submit, " 登录 "));
+
+            self.form_footer = Paragraph::new(vec![Line::default(), Line::from(buttons).centered(), Line::default(), Line::from(self.form_status.clone()).centered()]).style(*style);
+            self.login_page = Paragraph::default()
+                .block(
+                    Block::default()
+                        .title(Line::from(format!("Netease Cloud Music - {}", self.login_method.name())).left_aligned())
+                        .title_bottom(Line::from("j/k 切换输入项，Enter 开始输入或确认，输入时 Esc 结束输入").right_aligned())
+                        .borders(Borders::ALL),
+                )
+                .style(*style);
+        }
     }
 
     fn draw(&self, frame: &mut Frame, chunk: Rect) {
-        frame.render_widget(&self.login_page, chunk);
+        let chunks = Layout::default().direction(Direction::Vertical).constraints([Constraint::Length(1), Constraint::Min(0)]).split(chunk);
+        frame.render_widget(&self.method_tabs, chunks[0]);
+
+        frame.render_widget(&self.login_page, chunks[1]);
+        if self.login_method != LoginMethod::Qr {
+            // 输入框绘制在表单边框内
+            let form_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(3), Constraint::Length(3), Constraint::Min(0)])
+                .split(chunks[1].inner(Margin { vertical: 1, horizontal: 2 }));
+            frame.render_widget(&self.account_input, form_chunks[0]);
+            frame.render_widget(&self.secret_input, form_chunks[1]);
+            frame.render_widget(&self.form_footer, form_chunks[2]);
+        }
     }
 }
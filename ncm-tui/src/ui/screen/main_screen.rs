@@ -1,15 +1,19 @@
+use crate::{actions, command_queue, ncm_client, player};
 use crate::config::Command;
-use crate::ui::panel::{LyricPanel, PanelFocusedStatus, PlaylistPanel};
+use crate::ui::panel::{CommentsPanel, CoverPanel, LyricPanel, PanelFocusedStatus, PlaylistPanel};
 use crate::ui::Controller;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use ratatui::layout::Rect;
 use ratatui::prelude::*;
 use ratatui::Frame;
 
+/// 右半屏高度不小于该行数时才在歌词上方显示封面，避免挤占歌词
+const COVER_MIN_SCREEN_HEIGHT: u16 = 30;
+
 #[derive(PartialEq)]
 enum Panels {
     Playlist,
-    Lyric,
+    Lyric, // 右侧面板，显示歌词或评论
 }
 
 #[derive(PartialEq)]
@@ -25,6 +29,9 @@ pub struct MainScreen<'a> {
     //
     playlist_panel: PlaylistPanel<'a>,
     lyric_panel: LyricPanel<'a>,
+    cover_panel: CoverPanel,
+    comments_panel: CommentsPanel<'a>,
+    show_comments: bool, // 右侧显示评论（否则显示歌词）
 }
 
 impl<'a> MainScreen<'a> {
@@ -33,6 +40,9 @@ impl<'a> MainScreen<'a> {
             current_focus_panel: FocusPanel::PlaylistOutside,
             playlist_panel: PlaylistPanel::new(PanelFocusedStatus::Outside),
             lyric_panel: LyricPanel::new(PanelFocusedStatus::Nop),
+            cover_panel: CoverPanel::new(),
+            comments_panel: CommentsPanel::new(PanelFocusedStatus::Nop),
+            show_comments: false,
         }
     }
 }
@@ -45,10 +55,22 @@ impl<'a> Controller for MainScreen<'a> {
         if self.playlist_panel.update_model().await? {
             result = Ok(true);
         }
+        if self.playlist_panel.sync_current_playlist_growth().await? {
+            result = Ok(true);
+        }
 
         // song
-        if self.lyric_panel.update_model().await? {
-            result = Ok(true);
+        if self.show_comments {
+            if self.comments_panel.update_model().await? {
+                result = Ok(true);
+            }
+        } else {
+            if self.lyric_panel.update_model().await? {
+                result = Ok(true);
+            }
+            if self.cover_panel.update_model().await? {
+                result = Ok(true);
+            }
         }
 
         result
@@ -77,7 +99,7 @@ impl<'a> Controller for MainScreen<'a> {
                 self.playlist_panel.handle_event(cmd).await?;
             },
             (Down | Up, LyricInside) => {
-                self.lyric_panel.handle_event(cmd).await?;
+                self.right_panel_handle_event(cmd).await?;
             },
             //
             (NextPanel, PlaylistOutside) => {
@@ -96,7 +118,7 @@ impl<'a> Controller for MainScreen<'a> {
             (EnterOrPlay | Play, PlaylistInside) => {
                 self.playlist_panel.handle_event(cmd).await?;
             },
-            (EnterOrPlay | Play, LyricInside) => {
+            (EnterOrPlay | Play, LyricInside) if !self.show_comments => {
                 self.lyric_panel.handle_event(cmd).await?;
                 self.focus_panel_outside(Panels::Lyric);
             },
@@ -111,7 +133,7 @@ impl<'a> Controller for MainScreen<'a> {
                 self.focus_panel_inside(Panels::Playlist);
             },
             (GoToTop | GoToBottom, LyricOutside | LyricInside) => {
-                self.lyric_panel.handle_event(cmd).await?;
+                self.right_panel_handle_event(cmd).await?;
                 self.focus_panel_inside(Panels::Lyric);
             },
             //
@@ -124,6 +146,87 @@ impl<'a> Controller for MainScreen<'a> {
                 self.playlist_panel.handle_event(cmd).await?;
             },
             //
+            (Like(_) | ToggleLike, PlaylistInside) => {
+                self.playlist_panel.handle_event(cmd).await?;
+            },
+            (Like(like), _) => {
+                actions::like_current_song(Some(like)).await?;
+            },
+            (ToggleLike, _) => {
+                actions::like_current_song(None).await?;
+            },
+            //
+            (ViewAlbum, PlaylistInside) => {
+                actions::view_album(self.playlist_panel.get_selected_song()).await;
+            },
+            (ViewAlbum, _) => {
+                actions::view_album(None).await;
+            },
+            //
+            (ViewArtist(artist_index), PlaylistInside) => {
+                actions::view_artist(self.playlist_panel.get_selected_song(), artist_index).await?;
+            },
+            (ViewArtist(artist_index), _) => {
+                actions::view_artist(None, artist_index).await?;
+            },
+            //
+            (AddToSonglist(songlist_name), PlaylistInside) => {
+                actions::add_to_songlist(&songlist_name, self.playlist_panel.get_selected_song()).await?;
+            },
+            (AddToSonglist(songlist_name), _) => {
+                actions::add_to_songlist(&songlist_name, None).await?;
+            },
+            // 从当前播放列表对应的歌单中删除所选歌曲
+            (RemoveFromSonglist, PlaylistInside) => {
+                if let Some(song) = self.playlist_panel.get_selected_song() {
+                    let (is_radio_mode, current_playlist_id) = {
+                        let player_guard = player.lock().await;
+                        (player_guard.is_radio_mode(), player_guard.current_playlist_id())
+                    };
+                    // 私人FM/心动模式的播放列表由推荐生成，不对应歌单（心动模式的 id 为“我喜欢的音乐”）
+                    if is_radio_mode {
+                        return Err(anyhow!("私人FM和心动模式下不能从歌单删除歌曲"));
+                    }
+                    actions::remove_from_songlist(current_playlist_id, &song).await?;
+
+                    command_queue.lock().await.push_back(RefreshPlaylist);
+                }
+            },
+            (RemoveFromSonglist, _) => {
+                return Err(anyhow!("请先在播放列表中选中要删除的歌曲"));
+            },
+            //
+            (ToggleComments, _) => {
+                self.show_comments = !self.show_comments;
+                // 聚焦在右侧面板时，聚焦状态转移到新显示的面板
+                match self.current_focus_panel {
+                    LyricOutside => self.focus_panel_outside(Panels::Lyric),
+                    LyricInside => self.focus_panel_inside(Panels::Lyric),
+                    _ => {},
+                }
+            },
+            (SortComments(sort), _) => {
+                self.show_comments = true;
+                self.comments_panel.set_sort(sort).await;
+                self.focus_panel_inside(Panels::Lyric);
+            },
+            (PostComment(content), _) => {
+                let song = player.lock().await.current_song().clone().ok_or_else(|| anyhow!("请先播放要评论的歌曲"))?;
+                ncm_client.lock().await.post_song_comment(song.id, &content, None).await?;
+
+                self.show_comments = true;
+                self.comments_panel.reload().await;
+                self.focus_panel_inside(Panels::Lyric);
+            },
+            (ReplyComment(content), LyricInside) if self.show_comments => {
+                let song = player.lock().await.current_song().clone().ok_or_else(|| anyhow!("请先播放要评论的歌曲"))?;
+                let comment = self.comments_panel.get_selected_comment().ok_or_else(|| anyhow!("请先选中要回复的评论"))?;
+                ncm_client.lock().await.post_song_comment(song.id, &content, Some(comment.id)).await?;
+            },
+            (ReplyComment(_), _) => {
+                return Err(anyhow!("请在评论面板中选中要回复的评论"));
+            },
+            //
             (_, _) => return Ok(false),
         }
 
@@ -134,6 +237,10 @@ impl<'a> Controller for MainScreen<'a> {
         self.playlist_panel.update_view(style);
 
         self.lyric_panel.update_view(style);
+
+        self.cover_panel.update_view(style);
+
+        self.comments_panel.update_view(style);
     }
 
     fn draw(&self, frame: &mut Frame, chunk: Rect) {
@@ -146,8 +253,20 @@ impl<'a> Controller for MainScreen<'a> {
         // 在左半屏渲染 playlist_panel
         self.playlist_panel.draw(frame, chunks[0]);
 
-        // 在右半屏渲染 lyric_panel
-        self.lyric_panel.draw(frame, chunks[1]);
+        // 在右半屏渲染 comments_panel 或 lyric_panel，显示歌词且高度足够时在上方渲染 cover_panel
+        if self.show_comments {
+            self.comments_panel.draw(frame, chunks[1]);
+        } else if self.cover_panel.has_cover() && chunks[1].height >= COVER_MIN_SCREEN_HEIGHT {
+            let right_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(40), Constraint::Percentage(60)].as_ref())
+                .split(chunks[1]);
+
+            self.cover_panel.draw(frame, right_chunks[0]);
+            self.lyric_panel.draw(frame, right_chunks[1]);
+        } else {
+            self.lyric_panel.draw(frame, chunks[1]);
+        }
     }
 }
 
@@ -159,11 +278,18 @@ impl<'a> MainScreen<'a> {
                 self.current_focus_panel = FocusPanel::PlaylistOutside;
                 self.playlist_panel.focused_status = PanelFocusedStatus::Outside;
                 self.lyric_panel.focused_status = PanelFocusedStatus::Nop;
+                self.comments_panel.focused_status = PanelFocusedStatus::Nop;
             },
             Panels::Lyric => {
                 self.current_focus_panel = FocusPanel::LyricOutside;
                 self.playlist_panel.focused_status = PanelFocusedStatus::Nop;
-                self.lyric_panel.focused_status = PanelFocusedStatus::Outside;
+                if self.show_comments {
+                    self.lyric_panel.focused_status = PanelFocusedStatus::Nop;
+                    self.comments_panel.focused_status = PanelFocusedStatus::Outside;
+                } else {
+                    self.lyric_panel.focused_status = PanelFocusedStatus::Outside;
+                    self.comments_panel.focused_status = PanelFocusedStatus::Nop;
+                }
             },
         }
     }
@@ -174,12 +300,28 @@ impl<'a> MainScreen<'a> {
                 self.current_focus_panel = FocusPanel::PlaylistInside;
                 self.playlist_panel.focused_status = PanelFocusedStatus::Inside;
                 self.lyric_panel.focused_status = PanelFocusedStatus::Nop;
+                self.comments_panel.focused_status = PanelFocusedStatus::Nop;
             },
             Panels::Lyric => {
                 self.current_focus_panel = FocusPanel::LyricInside;
                 self.playlist_panel.focused_status = PanelFocusedStatus::Nop;
-                self.lyric_panel.focused_status = PanelFocusedStatus::Inside;
+                if self.show_comments {
+                    self.lyric_panel.focused_status = PanelFocusedStatus::Nop;
+                    self.comments_panel.focused_status = PanelFocusedStatus::Inside;
+                } else {
+                    self.lyric_panel.focused_status = PanelFocusedStatus::Inside;
+                    self.comments_panel.focused_status = PanelFocusedStatus::Nop;
+                }
             },
         }
     }
+
+    /// 右侧面板（歌词或评论）处理事件
+    async fn right_panel_handle_event(&mut self, cmd: Command) -> Result<bool> {
+        if self.show_comments {
+            self.comments_panel.handle_event(cmd).await
+        } else {
+            self.lyric_panel.handle_event(cmd).await
+        }
+    }
 }
@@ -0,0 +1,359 @@
+use crate::config::{Command, ScreenEnum};
+use crate::ui::panel::{PanelFocusedStatus, PlaylistPanel, SearchPanel};
+use crate::ui::Controller;
+use crate::{actions, command_queue, ncm_client, player};
+use anyhow::Result;
+use ncm_api::model::{SearchResult, SearchType, Song, Songlist};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::prelude::Style;
+use ratatui::Frame;
+
+#[derive(PartialEq)]
+enum Panels {
+    SearchResult,
+    Browse,
+}
+
+#[derive(PartialEq)]
+enum FocusPanel {
+    SearchResultOutside,
+    SearchResultInside,
+    BrowseOutside,
+    BrowseInside,
+}
+
+pub struct SearchScreen<'a> {
+    current_focus_panel: FocusPanel,
+    //
+    browsing_name: String,
+    browsing_songs: Vec<Song>,
+    //
+    search_panel: SearchPanel<'a>,
+    browse_panel: PlaylistPanel<'a>,
+}
+
+impl<'a> SearchScreen<'a> {
+    pub fn new(_normal_style: &Style) -> Self {
+        Self {
+            current_focus_panel: FocusPanel::SearchResultOutside,
+            browsing_name: String::new(),
+            browsing_songs: Vec::new(),
+            search_panel: SearchPanel::new(PanelFocusedStatus::Outside),
+            browse_panel: PlaylistPanel::new(PanelFocusedStatus::Nop),
+        }
+    }
+}
+
+impl<'a> Controller for SearchScreen<'a> {
+    async fn update_model(&mut self) -> Result<bool> {
+        let mut result = Ok(false);
+
+        // search result
+        if self.search_panel.update_model().await? {
+            result = Ok(true);
+        }
+
+        // browse
+        if self.browse_panel.update_model().await? {
+            result = Ok(true);
+        }
+
+        result
+    }
+
+    async fn handle_event(&mut self, cmd: Command) -> Result<bool> {
+        use Command::*;
+        use FocusPanel::*;
+
+        match (cmd.clone(), &self.current_focus_panel) {
+            //
+            (GlobalSearch(keywords), _) => {
+                self.search_panel.search(&keywords).await?;
+                self.focus_panel_inside(Panels::SearchResult);
+            },
+            (SwitchSearchType, _) => {
+                self.search_panel.switch_search_type().await?;
+                self.focus_panel_inside(Panels::SearchResult);
+            },
+            //
+            (Esc, SearchResultInside) => {
+                self.focus_panel_outside(Panels::SearchResult);
+            },
+            (Esc, BrowseInside) => {
+                self.focus_panel_outside(Panels::Browse);
+            },
+            //
+            (Down | Up, SearchResultOutside) => {
+                self.focus_panel_inside(Panels::SearchResult);
+            },
+            (Down | Up, BrowseOutside) => {
+                self.focus_panel_inside(Panels::Browse);
+            },
+            (Down | Up, SearchResultInside) => {
+                self.search_panel.handle_event(cmd).await?;
+            },
+            (Down | Up, BrowseInside) => {
+                self.browse_panel.handle_event(cmd).await?;
+            },
+            //
+            (NextPanel, SearchResultOutside) if self.is_browsable() => {
+                self.focus_panel_outside(Panels::Browse);
+            },
+            (PrevPanel, BrowseOutside) => {
+                self.focus_panel_outside(Panels::SearchResult);
+            },
+            //
+            (EnterOrPlay, SearchResultOutside) => {
+                self.focus_panel_inside(Panels::SearchResult);
+            },
+            (EnterOrPlay, BrowseOutside) => {
+                self.focus_panel_inside(Panels::Browse);
+            },
+            // 单曲/歌词搜索结果：以搜索结果作为播放列表，并从选中歌曲开始播放
+            // 专辑/歌手/歌单搜索结果：在浏览面板中打开
+            (EnterOrPlay | Play, SearchResultInside) => {
+                if let Some(selected_index) = self.search_panel.get_selected_index() {
+                    let playlist_name = format!("搜索: {}", self.search_panel.keywords());
+                    match self.search_panel.search_result() {
+                        SearchResult::Songs(songs) => {
+                            self.play_songs(playlist_name, songs.clone(), selected_index).await?;
+                        },
+                        SearchResult::Lyrics(lyrics) => {
+                            let songs = lyrics.iter().map(|(song, _)| song.clone()).collect();
+                            self.play_songs(playlist_name, songs, selected_index).await?;
+                        },
+                        _ => {
+                            self.open_selected_in_browse_panel(selected_index).await?;
+
+                            if matches!(cmd, Play) {
+                                // 切换到浏览的歌曲列表并开始播放
+                                player.lock().await.switch_custom_playlist(self.browsing_name.clone(), self.browsing_songs.clone());
+
+                                let mut command_queue_guard = command_queue.lock().await;
+                                command_queue_guard.push_back(StartPlay);
+                                command_queue_guard.push_back(GotoScreen(ScreenEnum::Main));
+                                command_queue_guard.push_back(RefreshPlaylist);
+                                command_queue_guard.push_back(WhereIsThisSong);
+                                drop(command_queue_guard);
+                            } else {
+                                self.focus_panel_inside(Panels::Browse);
+                            }
+                        },
+                    }
+                }
+            },
+            // 切换到浏览的歌曲列表并从选中歌曲开始播放
+            (EnterOrPlay | Play, BrowseInside) => {
+                player.lock().await.switch_custom_playlist(self.browsing_name.clone(), self.browsing_songs.clone());
+
+                // 播放选中歌曲
+                self.browse_panel.handle_event(cmd).await?;
+
+                // 返回 main_screen ，刷新播放列表显示
+                let mut command_queue_guard = command_queue.lock().await;
+                command_queue_guard.push_back(GotoScreen(ScreenEnum::Main));
+                command_queue_guard.push_back(RefreshPlaylist);
+                command_queue_guard.push_back(WhereIsThisSong);
+                drop(command_queue_guard);
+            },
+            //
+            (GoToTop | GoToBottom, SearchResultOutside | SearchResultInside) => {
+                self.search_panel.handle_event(cmd).await?;
+                self.focus_panel_inside(Panels::SearchResult);
+            },
+            (GoToTop | GoToBottom, BrowseOutside | BrowseInside) => {
+                self.browse_panel.handle_event(cmd).await?;
+                self.focus_panel_inside(Panels::Browse);
+            },
+            //
+            (Like(_) | ToggleLike, BrowseInside) => {
+                self.browse_panel.handle_event(cmd).await?;
+            },
+            (Like(like), _) => {
+                actions::like_current_song(Some(like)).await?;
+            },
+            (ToggleLike, _) => {
+                actions::like_current_song(None).await?;
+            },
+            //
+            (ViewAlbum, SearchResultInside) => {
+                actions::view_album(self.selected_result_song()).await;
+            },
+            (ViewAlbum, BrowseInside) => {
+                actions::view_album(self.browse_panel.get_selected_song()).await;
+            },
+            (ViewAlbum, _) => {
+                actions::view_album(None).await;
+            },
+            //
+            (ViewArtist(artist_index), SearchResultInside) => {
+                actions::view_artist(self.selected_result_song(), artist_index).await?;
+            },
+            (ViewArtist(artist_index), BrowseInside) => {
+                actions::view_artist(self.browse_panel.get_selected_song(), artist_index).await?;
+            },
+            (ViewArtist(artist_index), _) => {
+                actions::view_artist(None, artist_index).await?;
+            },
+            //
+            (AddToSonglist(songlist_name), SearchResultInside) => {
+                actions::add_to_songlist(&songlist_name, self.selected_result_song()).await?;
+            },
+            (AddToSonglist(songlist_name), BrowseInside) => {
+                actions::add_to_songlist(&songlist_name, self.browse_panel.get_selected_song()).await?;
+            },
+            (AddToSonglist(songlist_name), _) => {
+                actions::add_to_songlist(&songlist_name, None).await?;
+            },
+            //
+            (Subscribe(subscribe), SearchResultInside) => {
+                if let Some(songlist) = self.selected_result_songlist() {
+                    actions::subscribe_songlist(&songlist, Some(subscribe)).await?;
+                }
+            },
+            (ToggleSubscribe, SearchResultInside) => {
+                if let Some(songlist) = self.selected_result_songlist() {
+                    actions::subscribe_songlist(&songlist, None).await?;
+                }
+            },
+            //
+            (_, _) => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn update_view(&mut self, style: &Style) {
+        self.search_panel.update_view(style);
+
+        self.browse_panel.update_view(style);
+    }
+
+    fn draw(&self, frame: &mut Frame, chunk: Rect) {
+        if self.is_browsable() {
+            // 分为左右两个面板
+            let chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+                .split(chunk);
+
+            // 在左半屏渲染 search_panel
+            self.search_panel.draw(frame, chunks[0]);
+
+            // 在右半屏渲染 browse_panel
+            self.browse_panel.draw(frame, chunks[1]);
+        } else {
+            self.search_panel.draw(frame, chunk);
+        }
+    }
+}
+
+/// private
+impl<'a> SearchScreen<'a> {
+    /// 当前搜索类型的结果是否可以在浏览面板中打开
+    fn is_browsable(&self) -> bool {
+        matches!(self.search_panel.search_type(), SearchType::Album | SearchType::Artist | SearchType::Songlist)
+    }
+
+    /// 单曲/歌词搜索结果中选中的歌曲
+    fn selected_result_song(&self) -> Option<Song> {
+        let selected_index = self.search_panel.get_selected_index()?;
+        match self.search_panel.search_result() {
+            SearchResult::Songs(songs) => songs.get(selected_index).cloned(),
+            SearchResult::Lyrics(lyrics) => lyrics.get(selected_index).map(|(song, _)| song.clone()),
+            _ => None,
+        }
+    }
+
+    /// 歌单搜索结果中选中的歌单
+    fn selected_result_songlist(&self) -> Option<Songlist> {
+        let selected_index = self.search_panel.get_selected_index()?;
+        match self.search_panel.search_result() {
+            SearchResult::Songlists(songlists) => songlists.get(selected_index).cloned(),
+            _ => None,
+        }
+    }
+
+    /// 以给定歌曲作为播放列表，并从选中歌曲开始播放
+    async fn play_songs(&self, playlist_name: String, songs: Vec<Song>, selected_index: usize) -> Result<()> {
+        let mut player_guard = player.lock().await;
+        player_guard.switch_custom_playlist(playlist_name, songs);
+        player_guard.play_particularly_now(selected_index, &*ncm_client.lock().await).await?;
+        drop(player_guard);
+
+        // 返回 main_screen ，刷新播放列表显示
+        let mut command_queue_guard = command_queue.lock().await;
+        command_queue_guard.push_back(Command::GotoScreen(ScreenEnum::Main));
+        command_queue_guard.push_back(Command::RefreshPlaylist);
+        command_queue_guard.push_back(Command::WhereIsThisSong);
+        drop(command_queue_guard);
+
+        Ok(())
+    }
+
+    /// 加载选中的专辑/歌手/歌单，并在浏览面板中显示
+    async fn open_selected_in_browse_panel(&mut self, selected_index: usize) -> Result<()> {
+        let ncm_client_guard = ncm_client.lock().await;
+
+        let (browsing_name, browsing_songs) = match self.search_panel.search_result() {
+            SearchResult::Albums(albums) => match albums.get(selected_index) {
+                Some(album) => {
+                    let album = ncm_client_guard.get_album(album.id).await?;
+                    (format!("专辑: {}", album.name), album.songs)
+                },
+                None => return Ok(()),
+            },
+            SearchResult::Artists(artists) => match artists.get(selected_index) {
+                Some(artist) => (format!("歌手: {}", artist.name), ncm_client_guard.get_artist_top_songs(artist.id).await?),
+                None => return Ok(()),
+            },
+            SearchResult::Songlists(songlists) => match songlists.get(selected_index) {
+                Some(songlist) => {
+                    let mut songlist = songlist.clone();
+                    ncm_client_guard.load_songlist_songs(&mut songlist).await?;
+                    (format!("歌单: {}", songlist.name), songlist.songs)
+                },
+                None => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+
+        drop(ncm_client_guard);
+
+        self.browse_panel.set_model(&browsing_name, &browsing_songs);
+        self.browsing_name = browsing_name;
+        self.browsing_songs = browsing_songs;
+
+        Ok(())
+    }
+
+    fn focus_panel_outside(&mut self, to_panel: Panels) {
+        match to_panel {
+            Panels::SearchResult => {
+                self.current_focus_panel = FocusPanel::SearchResultOutside;
+                self.search_panel.focused_status = PanelFocusedStatus::Outside;
+                self.browse_panel.focused_status = PanelFocusedStatus::Nop;
+            },
+            Panels::Browse => {
+                self.current_focus_panel = FocusPanel::BrowseOutside;
+                self.search_panel.focused_status = PanelFocusedStatus::Nop;
+                self.browse_panel.focused_status = PanelFocusedStatus::Outside;
+            },
+        }
+    }
+
+    fn focus_panel_inside(&mut self, to_panel: Panels) {
+        match to_panel {
+            Panels::SearchResult => {
+                self.current_focus_panel = FocusPanel::SearchResultInside;
+                self.search_panel.focused_status = PanelFocusedStatus::Inside;
+                self.browse_panel.focused_status = PanelFocusedStatus::Nop;
+            },
+            Panels::Browse => {
+                self.current_focus_panel = FocusPanel::BrowseInside;
+                self.search_panel.focused_status = PanelFocusedStatus::Nop;
+                self.browse_panel.focused_status = PanelFocusedStatus::Inside;
+            },
+        }
+    }
+}
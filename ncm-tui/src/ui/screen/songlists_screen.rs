@@ -1,7 +1,8 @@
 use crate::config::{Command, ScreenEnum};
 use crate::ui::panel::{PanelFocusedStatus, PlaylistPanel, SonglistsPanel};
 use crate::ui::Controller;
-use crate::{command_queue, ncm_client, player};
+use crate::{actions, command_queue, ncm_client, player};
+use anyhow::anyhow;
 use log::debug;
 use ncm_api::model::Songlist;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
@@ -117,7 +118,7 @@ impl<'a> Controller for SonglistsScreen<'a> {
                     debug!("切换到 {} 号歌单", selected_songlist_index);
 
                     // 切换当前播放列表
-                    player.lock().await.switch_playlist(selected_songlist_index, ncm_client.lock().await).await?;
+                    player.lock().await.switch_playlist(selected_songlist_index, &*ncm_client.lock().await).await?;
 
                     // 播放选中歌曲
                     self.songlist_content_panel.handle_event(cmd).await?;
@@ -136,7 +137,7 @@ impl<'a> Controller for SonglistsScreen<'a> {
                     debug!("切换到 {} 号歌单", selected_songlist_index);
 
                     // 切换当前播放列表
-                    player.lock().await.switch_playlist(selected_songlist_index, ncm_client.lock().await).await?;
+                    player.lock().await.switch_playlist(selected_songlist_index, &*ncm_client.lock().await).await?;
 
                     // 开始自动播放，返回 main_screen ，刷新播放列表显示
                     let mut command_queue_guard = command_queue.lock().await;
@@ -166,6 +167,85 @@ impl<'a> Controller for SonglistsScreen<'a> {
                 self.focus_panel_inside(Panels::SonglistContent);
             },
 
+            //
+            (Like(_) | ToggleLike, SonglistContentInside) => {
+                self.songlist_content_panel.handle_event(cmd).await?;
+            },
+            (Like(like), _) => {
+                actions::like_current_song(Some(like)).await?;
+            },
+            (ToggleLike, _) => {
+                actions::like_current_song(None).await?;
+            },
+            //
+            (ViewAlbum, SonglistContentInside) => {
+                actions::view_album(self.songlist_content_panel.get_selected_song()).await;
+            },
+            (ViewAlbum, _) => {
+                actions::view_album(None).await;
+            },
+            //
+            (ViewArtist(artist_index), SonglistContentInside) => {
+                actions::view_artist(self.songlist_content_panel.get_selected_song(), artist_index).await?;
+            },
+            (ViewArtist(artist_index), _) => {
+                actions::view_artist(None, artist_index).await?;
+            },
+
+            //
+            (AddToSonglist(songlist_name), SonglistContentInside) => {
+                actions::add_to_songlist(&songlist_name, self.songlist_content_panel.get_selected_song()).await?;
+            },
+            (AddToSonglist(songlist_name), _) => {
+                actions::add_to_songlist(&songlist_name, None).await?;
+            },
+            (RemoveFromSonglist, SonglistContentInside) => {
+                if let (Some(songlist), Some(song)) = (self.current_selected_songlist.as_mut(), self.songlist_content_panel.get_selected_song()) {
+                    actions::remove_from_songlist(songlist.id, &song).await?;
+
+                    songlist.songs.retain(|s| s.id != song.id);
+                    self.songlist_content_panel.set_model(&songlist.name, &songlist.songs);
+                }
+            },
+            (RemoveFromSonglist, _) => {
+                return Err(anyhow!("请先在歌单内容中选中要删除的歌曲"));
+            },
+            (Subscribe(subscribe), _) => {
+                if let Some(songlist) = self.songlist_candidates_panel.get_selected_songlist() {
+                    actions::subscribe_songlist(&songlist, Some(subscribe)).await?;
+                }
+            },
+            (ToggleSubscribe, _) => {
+                if let Some(songlist) = self.songlist_candidates_panel.get_selected_songlist() {
+                    actions::subscribe_songlist(&songlist, None).await?;
+                }
+            },
+            (DeleteSonglist(name), _) => {
+                if let Some(songlist) = self.songlist_candidates_panel.get_selected_songlist() {
+                    // 删除不可恢复，要求输入选中歌单的名称确认
+                    if songlist.name != name {
+                        return Err(anyhow!("选中的歌单是`{}`，请输入`songlist delete {}`确认删除", songlist.name, songlist.name));
+                    }
+                    actions::delete_songlist(&songlist).await?;
+
+                    // 清空已删除歌单的内容显示
+                    if self.current_selected_songlist.as_ref().is_some_and(|s| s.id == songlist.id) {
+                        self.current_selected_songlist = None;
+                        self.songlist_content_panel.set_model(&String::new(), &Vec::new());
+                    }
+                }
+            },
+            (RenameSonglist(name), _) => {
+                if let Some(songlist) = self.songlist_candidates_panel.get_selected_songlist() {
+                    actions::rename_songlist(&songlist, &name).await?;
+
+                    if let Some(current_selected_songlist) = self.current_selected_songlist.as_mut().filter(|s| s.id == songlist.id) {
+                        current_selected_songlist.name = name;
+                        self.songlist_content_panel.set_model(&current_selected_songlist.name, &current_selected_songlist.songs);
+                    }
+                }
+            },
+
             //
             (_, _) => {
                 return Ok(false);
@@ -1,5 +1,7 @@
 mod bottom_bar;
 mod command_line;
+mod cover_art;
 
 pub use bottom_bar::*;
 pub use command_line::*;
+pub use cover_art::*;
@@ -1,7 +1,8 @@
-use crate::config::Command;
-use crate::player;
+use crate::config::{quality_level_label, Command};
+use crate::{cloud_upload, player};
 use crate::ui::Controller;
 use anyhow::Result;
+use ncm_api::model::QualityLevel;
 use ratatui::layout::{Layout, Rect};
 use ratatui::prelude::{Constraint, Direction, Style};
 use ratatui::style::palette::tailwind;
@@ -17,7 +18,8 @@ pub struct BottomBar<'a> {
     playback_label: String,
     song_name: Option<String>,
     singer_name: Option<String>,
-    song_quality_level: Option<String>,
+    song_quality_level: Option<QualityLevel>,
+    upload_label: Option<String>,
     //
     volume: f64,
 
@@ -36,6 +38,7 @@ impl<'a> BottomBar<'a> {
             song_name: None,
             singer_name: None,
             song_quality_level: None,
+            upload_label: None,
             volume: 0.0,
             control_bar: Paragraph::default(),
             playback_bar: Gauge::default(),
@@ -71,10 +74,23 @@ impl<'a> Controller for BottomBar<'a> {
         };
         if let Some(song) = player_guard.current_song().clone() {
             self.song_name = Some(song.name.clone());
-            self.singer_name = Some(song.singer.clone());
-            self.song_quality_level = Some(song.quality_level.clone());
+            self.singer_name = Some(song.artists_name());
+            self.song_quality_level = song.quality_level;
+        } else {
+            // 无当前歌曲（如登出后）时不显示上一首歌曲的信息
+            self.song_name = None;
+            self.singer_name = None;
+            self.song_quality_level = None;
         }
 
+        // 云盘上传进度
+        self.upload_label = cloud_upload.lock().await.as_ref().map(|progress| match progress.result() {
+            Some(Ok(_)) => format!("\u{2601} {} 上传完成", progress.file_name),
+            Some(Err(err)) => format!("\u{2601} {} 上传失败：{}", progress.file_name, err),
+            None if progress.is_processing() => format!("\u{2601} {} 云盘处理中", progress.file_name),
+            None => format!("\u{2601} 上传 {} {:.0}%", progress.file_name, progress.ratio() * 100.0),
+        });
+
         // volume_bar
         self.volume = player_guard.volume();
 
@@ -92,11 +108,16 @@ impl<'a> Controller for BottomBar<'a> {
         self.playback_bar = Gauge::default()
             .block({
                 let mut block = Block::default().borders(Borders::ALL).style(*style);
-                if let (Some(song_name), Some(artist_name), Some(song_quality_level)) = (self.song_name.clone(), self.singer_name.clone(), self.song_quality_level.clone()) {
+                if let (Some(song_name), Some(artist_name)) = (self.song_name.clone(), self.singer_name.clone()) {
                     block = block
                         .title_top(Line::from(format!("{}", song_name)).centered())
-                        .title_bottom(Line::from(format!("{}", artist_name)).centered())
-                        .title_bottom(Line::from(format!("音质:{}", song_quality_level)).right_aligned());
+                        .title_bottom(Line::from(format!("{}", artist_name)).centered());
+                    if let Some(song_quality_level) = self.song_quality_level {
+                        block = block.title_bottom(Line::from(format!("音质:{}", quality_level_label(song_quality_level))).right_aligned());
+                    }
+                }
+                if let Some(upload_label) = self.upload_label.clone() {
+                    block = block.title_top(Line::from(upload_label).left_aligned());
                 }
                 block
             })
@@ -15,6 +15,7 @@ use unicode_width::UnicodeWidthStr;
 const NORMAL_TEXT: &str = " NORMAL ";
 const COMMAND_TEXT: &str = " COMMAND ";
 const SEARCH_TEXT: &str = " SEARCH ";
+const INPUT_TEXT: &str = " INPUT ";
 
 pub struct CommandLine<'a> {
     //
@@ -40,7 +41,7 @@ impl<'a> CommandLine<'a> {
             mode_label: Line::default(),
             colon_line: Line::default(),
             interactive_area: TextArea::default(),
-            tabs: Tabs::new(vec!["1.播放", "2.歌单", "0.help", "登录"])
+            tabs: Tabs::new(vec!["1.播放", "2.歌单", "3.搜索", "0.help", "登录"])
                 .highlight_style(ITEM_SELECTED_STYLE)
                 .padding("", "")
                 .select(0)
@@ -72,6 +73,14 @@ impl<'a> CommandLine<'a> {
         self.current_mode = String::from(SEARCH_TEXT);
     }
 
+    /// 输入框模式下按键输入到 screen 内的输入框，command_line 只显示模式
+    pub fn set_to_input_mode(&mut self) {
+        self.clear_content();
+        self.show_cursor = false;
+        self.show_colon = false;
+        self.current_mode = String::from(INPUT_TEXT);
+    }
+
     pub fn get_content(&self) -> String {
         self.interactive_area.lines()[0].clone()
     }
@@ -106,8 +115,9 @@ impl<'a> Controller for CommandLine<'a> {
             Command::GotoScreen(to_screen) => match to_screen {
                 ScreenEnum::Main => self.tabs.to_owned().select(0),
                 ScreenEnum::Songlists => self.tabs.to_owned().select(1),
-                ScreenEnum::Help => self.tabs.to_owned().select(2),
-                ScreenEnum::Login => self.tabs.to_owned().select(3),
+                ScreenEnum::Search => self.tabs.to_owned().select(2),
+                ScreenEnum::Help => self.tabs.to_owned().select(3),
+                ScreenEnum::Login => self.tabs.to_owned().select(4),
                 _ => self.tabs.to_owned().select(None),
             },
             _ => self.tabs.to_owned(),
@@ -122,6 +132,7 @@ impl<'a> Controller for CommandLine<'a> {
             NORMAL_TEXT => tailwind::RED.c600,
             COMMAND_TEXT => tailwind::YELLOW.c600,
             SEARCH_TEXT => tailwind::BLUE.c600,
+            INPUT_TEXT => tailwind::GREEN.c600,
             _ => tailwind::BLACK,
         });
 
@@ -145,7 +156,7 @@ impl<'a> Controller for CommandLine<'a> {
                 Constraint::Length(UnicodeWidthStr::width(self.current_mode.as_str()) as u16),
                 Constraint::Max(UnicodeWidthStr::width(if self.show_colon { ": " } else { "" }) as u16),
                 Constraint::Fill(1),
-                Constraint::Max(25),
+                Constraint::Max(32),
             ])
             .split(chunk);
 
@@ -0,0 +1,264 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use image::imageops::FilterType;
+use image::{Rgb, RgbImage};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use ratatui::widgets::Widget;
+use std::cell::RefCell;
+use std::io::Write;
+
+/// 终端不返回像素尺寸时假定的字符格大小（宽, 高）
+const FALLBACK_CELL_PIXEL_SIZE: (u32, u32) = (10, 20);
+/// kitty 图形协议每段数据的最大长度
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+/// 封面图片的输出方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    /// 上半块字符 `▀`，前景色为上半像素、背景色为下半像素，适用于任意真彩色终端
+    HalfBlocks,
+    /// sixel 图形
+    Sixel,
+    /// kitty 图形协议
+    Kitty,
+}
+
+impl GraphicsProtocol {
+    /// 优先使用环境变量 `NCM_TUI_GRAPHICS`（halfblocks / sixel / kitty）指定的方式，
+    /// 未指定时识别 kitty 图形协议的终端，其余终端使用半块字符
+    pub fn detect() -> Self {
+        match std::env::var("NCM_TUI_GRAPHICS").as_deref() {
+            Ok("sixel") => return GraphicsProtocol::Sixel,
+            Ok("kitty") => return GraphicsProtocol::Kitty,
+            Ok("halfblocks") => return GraphicsProtocol::HalfBlocks,
+            _ => {},
+        }
+
+        let term = std::env::var("TERM").unwrap_or_default();
+        let term_program = std::env::var("TERM_PROGRAM").unwrap_or_default();
+        if std::env::var("KITTY_WINDOW_ID").is_ok() || term == "xterm-kitty" || term == "xterm-ghostty" || term_program == "WezTerm" {
+            GraphicsProtocol::Kitty
+        } else {
+            GraphicsProtocol::HalfBlocks
+        }
+    }
+
+    /// 是否以终端图形输出（不随文字刷新，需要手动清除）
+    pub fn is_graphics(&self) -> bool {
+        *self != GraphicsProtocol::HalfBlocks
+    }
+}
+
+/// 清除终端中以图形协议绘制的图片
+///
+/// kitty 的图片位于文字之上，需发送删除命令；sixel 会被重绘的文字覆盖，由调用方清屏重绘即可
+pub fn clear_terminal_graphics(protocol: GraphicsProtocol) {
+    if protocol == GraphicsProtocol::Kitty {
+        let mut stdout = std::io::stdout();
+        let _ = stdout.write_all(b"\x1b_Ga=d,d=A,q=2\x1b\\");
+        let _ = stdout.flush();
+    }
+}
+
+/// 封面图片，绘制时按区域缩放并水平居中
+pub struct CoverArt {
+    image: RgbImage,
+    protocol: GraphicsProtocol,
+    // 上次绘制的区域和对应的输出（半块字符为 None），区域不变时复用，避免每帧重新缩放/编码
+    rendered: RefCell<Option<(Rect, RgbImage, Option<String>)>>,
+}
+
+impl CoverArt {
+    pub fn new(image: RgbImage, protocol: GraphicsProtocol) -> Self {
+        Self {
+            image,
+            protocol,
+            rendered: RefCell::new(None),
+        }
+    }
+
+    /// 图片在区域内实际占用的范围：一个字符格约为 1:2，正方形封面的宽度（列数）为高度（行数）的两倍
+    fn image_area(area: Rect) -> Rect {
+        let height = area.height.min(area.width / 2);
+        let width = height * 2;
+
+        Rect::new(area.x + (area.width - width) / 2, area.y, width, height)
+    }
+
+    /// 缩放并编码图片（区域变化时才重新计算）
+    fn render_cached(&self, image_area: Rect) {
+        let mut rendered = self.rendered.borrow_mut();
+        if rendered.as_ref().is_some_and(|(area, _, _)| *area == image_area) {
+            return;
+        }
+
+        let cols = image_area.width as u32;
+        let rows = image_area.height as u32;
+        *rendered = Some(match self.protocol {
+            GraphicsProtocol::HalfBlocks => {
+                // 每格上下两个像素
+                let resized = image::imageops::resize(&self.image, cols, rows * 2, FilterType::Triangle);
+                (image_area, resized, None)
+            },
+            GraphicsProtocol::Sixel | GraphicsProtocol::Kitty => {
+                let (cell_width, cell_height) = cell_pixel_size();
+                let resized = image::imageops::resize(&self.image, cols * cell_width, rows * cell_height, FilterType::Triangle);
+                let sequence = if self.protocol == GraphicsProtocol::Sixel {
+                    encode_sixel(&resized)
+                } else {
+                    encode_kitty(&resized, cols, rows)
+                };
+                (image_area, resized, Some(sequence))
+            },
+        });
+    }
+}
+
+impl Widget for &CoverArt {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let image_area = CoverArt::image_area(area);
+        if image_area.is_empty() {
+            return;
+        }
+
+        self.render_cached(image_area);
+        let rendered = self.rendered.borrow();
+        let Some((_, resized, sequence)) = rendered.as_ref() else {
+            return;
+        };
+
+        match sequence {
+            None => {
+                for y in 0..image_area.height {
+                    for x in 0..image_area.width {
+                        let top = resized.get_pixel(x as u32, y as u32 * 2);
+                        let bottom = resized.get_pixel(x as u32, y as u32 * 2 + 1);
+                        if let Some(cell) = buf.cell_mut((image_area.x + x, image_area.y + y)) {
+                            cell.set_symbol("\u{2580}").set_fg(rgb_color(top)).set_bg(rgb_color(bottom));
+                        }
+                    }
+                }
+            },
+            Some(sequence) => {
+                // 整段图形序列写在左上角的格子里，其余格子跳过，由终端绘制图片
+                for y in 0..image_area.height {
+                    for x in 0..image_area.width {
+                        if let Some(cell) = buf.cell_mut((image_area.x + x, image_area.y + y)) {
+                            if x == 0 && y == 0 {
+                                cell.set_symbol(sequence);
+                            } else {
+                                cell.set_skip(true);
+                            }
+                        }
+                    }
+                }
+            },
+        }
+    }
+}
+
+fn rgb_color(pixel: &Rgb<u8>) -> Color {
+    Color::Rgb(pixel[0], pixel[1], pixel[2])
+}
+
+/// 字符格的像素大小（宽, 高）
+fn cell_pixel_size() -> (u32, u32) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => {
+            ((size.width / size.columns) as u32, (size.height / size.rows) as u32)
+        },
+        _ => FALLBACK_CELL_PIXEL_SIZE,
+    }
+}
+
+/// 编码为 sixel 序列，颜色量化到 6x6x6 的调色板
+fn encode_sixel(image: &RgbImage) -> String {
+    let (width, height) = image.dimensions();
+    let palette_index = |pixel: &Rgb<u8>| -> usize {
+        let level = |c: u8| (c as usize * 5 + 127) / 255;
+        level(pixel[0]) * 36 + level(pixel[1]) * 6 + level(pixel[2])
+    };
+
+    let mut sixel = format!("\x1bPq\"1;1;{};{}", width, height);
+    for index in 0..216 {
+        let percent = |level: usize| level * 100 / 5;
+        sixel.push_str(&format!("#{};2;{};{};{}", index, percent(index / 36), percent(index / 6 % 6), percent(index % 6)));
+    }
+
+    // 每 6 行像素为一个 band，band 内逐个颜色输出一遍
+    for band_top in (0..height).step_by(6) {
+        let band_height = (height - band_top).min(6);
+        let mut band_colors: Vec<usize> = (0..width).flat_map(|x| (0..band_height).map(move |dy| (x, dy))).map(|(x, dy)| palette_index(image.get_pixel(x, band_top + dy))).collect();
+        band_colors.sort_unstable();
+        band_colors.dedup();
+
+        for color in band_colors {
+            sixel.push_str(&format!("#{}", color));
+
+            // 行程编码：连续相同的 sixel 字符写作 `!<次数><字符>`
+            let mut run: Option<(u8, usize)> = None;
+            for x in 0..width {
+                let mut bits = 0u8;
+                for dy in 0..band_height {
+                    if palette_index(image.get_pixel(x, band_top + dy)) == color {
+                        bits |= 1 << dy;
+                    }
+                }
+                let ch = 63 + bits;
+
+                run = match run {
+                    Some((run_ch, count)) if run_ch == ch => Some((run_ch, count + 1)),
+                    Some((run_ch, count)) => {
+                        push_sixel_run(&mut sixel, run_ch, count);
+                        Some((ch, 1))
+                    },
+                    None => Some((ch, 1)),
+                };
+            }
+            if let Some((run_ch, count)) = run {
+                push_sixel_run(&mut sixel, run_ch, count);
+            }
+
+            // 回到 band 开头，叠加下一个颜色
+            sixel.push('$');
+        }
+
+        // 下一个 band
+        sixel.push('-');
+    }
+    sixel.push_str("\x1b\\");
+
+    sixel
+}
+
+fn push_sixel_run(sixel: &mut String, ch: u8, count: usize) {
+    if count > 3 {
+        sixel.push_str(&format!("!{}{}", count, ch as char));
+    } else {
+        for _ in 0..count {
+            sixel.push(ch as char);
+        }
+    }
+}
+
+/// 编码为 kitty 图形协议序列（24 位 RGB 原始数据），图片缩放到 `cols` x `rows` 个字符格
+fn encode_kitty(image: &RgbImage, cols: u32, rows: u32) -> String {
+    let (width, height) = image.dimensions();
+    let payload = BASE64.encode(image.as_raw());
+    let chunks: Vec<&str> = payload.as_bytes().chunks(KITTY_CHUNK_SIZE).map(|chunk| std::str::from_utf8(chunk).unwrap_or_default()).collect();
+
+    // 先删除之前的图片，再传输并显示新图片（C=1 不移动光标）
+    let mut kitty = String::from("\x1b_Ga=d,d=A,q=2\x1b\\");
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = if i + 1 < chunks.len() { 1 } else { 0 };
+        if i == 0 {
+            kitty.push_str(&format!("\x1b_Ga=T,f=24,s={},v={},c={},r={},C=1,q=2,m={};{}\x1b\\", width, height, cols, rows, more, chunk));
+        } else {
+            kitty.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+        }
+    }
+
+    kitty
+}